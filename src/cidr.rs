@@ -0,0 +1,185 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use serde_with::DeserializeFromStr;
+
+/// A block of IP addresses sharing a prefix, such as `192.0.2.0/24`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, DeserializeFromStr)]
+pub struct Cidr {
+    address: IpAddr,
+    prefix: u8,
+}
+
+/// The bits of an address, aligned to the most significant bit of a `u128`
+fn address_bits(address: IpAddr) -> u128 {
+    match address {
+        IpAddr::V4(ip) => (u32::from(ip) as u128) << 96,
+        IpAddr::V6(ip) => u128::from(ip),
+    }
+}
+
+fn address_len(address: IpAddr) -> u8 {
+    match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// Whether two addresses of the same family share their first `prefix` bits
+fn prefix_matches(left: IpAddr, right: IpAddr, prefix: u8) -> bool {
+    left.is_ipv4() == right.is_ipv4()
+        && (address_bits(left) ^ address_bits(right))
+            .checked_shr(128 - prefix as u32)
+            .unwrap_or(0)
+            == 0
+}
+
+impl Cidr {
+    /// Constructs a new block, returns `None` if the prefix is longer than the address or bits
+    /// are set past it
+    pub fn new(address: IpAddr, prefix: u8) -> Option<Self> {
+        if prefix > address_len(address) {
+            return None;
+        }
+
+        let host_bits = address_bits(address)
+            .checked_shl(prefix as u32)
+            .unwrap_or(0);
+        if host_bits != 0 {
+            return None;
+        }
+
+        Some(Self { address, prefix })
+    }
+
+    /// The block of a given prefix length that an address is in, returns `None` if the prefix is
+    /// longer than the address
+    pub fn containing(address: IpAddr, prefix: u8) -> Option<Self> {
+        if prefix > address_len(address) {
+            return None;
+        }
+
+        let host_len = 128 - prefix as u32;
+        let bits = address_bits(address)
+            .checked_shr(host_len)
+            .and_then(|bits| bits.checked_shl(host_len))
+            .unwrap_or(0);
+
+        let address = match address {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from((bits >> 96) as u32)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+        };
+
+        Some(Self { address, prefix })
+    }
+
+    /// The first address of the block
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// The number of leading bits shared by addresses in the block
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Whether an address is in this block
+    pub fn contains(&self, address: IpAddr) -> bool {
+        prefix_matches(self.address, address, self.prefix)
+    }
+
+    /// Whether a whole subnet of a given prefix length is in this block
+    pub fn contains_subnet(&self, address: IpAddr, prefix: u8) -> bool {
+        prefix >= self.prefix && self.contains(address)
+    }
+}
+
+impl Display for Cidr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseCidrError;
+
+impl Display for ParseCidrError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "provided string was not a valid CIDR block")
+    }
+}
+
+impl Error for ParseCidrError {}
+
+impl FromStr for Cidr {
+    type Err = ParseCidrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix) = match s.split_once('/') {
+            Some((address, prefix)) => {
+                let address: IpAddr = address.parse().map_err(|_| ParseCidrError)?;
+                let prefix = prefix.parse().map_err(|_| ParseCidrError)?;
+                (address, prefix)
+            }
+            None => {
+                let address: IpAddr = s.parse().map_err(|_| ParseCidrError)?;
+                (address, address_len(address))
+            }
+        };
+
+        Self::new(address, prefix).ok_or(ParseCidrError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("192.0.2.0/24" => Some("192.0.2.0/24".to_string()); "ipv4")]
+    #[test_case("2001:db8::/32" => Some("2001:db8::/32".to_string()); "ipv6")]
+    #[test_case("192.0.2.1" => Some("192.0.2.1/32".to_string()); "single address")]
+    #[test_case("0.0.0.0/0" => Some("0.0.0.0/0".to_string()); "everything")]
+    #[test_case("192.0.2.1/24" => None; "host bits")]
+    #[test_case("192.0.2.0/33" => None; "prefix too long")]
+    #[test_case("192.0.2.0/" => None; "missing prefix")]
+    #[test_case("example/8" => None; "not an address")]
+    fn parse(s: &str) -> Option<String> {
+        s.parse::<Cidr>().ok().map(|cidr| cidr.to_string())
+    }
+
+    #[test_case("192.0.2.0/24", "192.0.2.255" => true; "ipv4 inside")]
+    #[test_case("192.0.2.0/24", "192.0.3.0" => false; "ipv4 outside")]
+    #[test_case("0.0.0.0/0", "203.0.113.1" => true; "ipv4 everything")]
+    #[test_case("2001:db8::/32", "2001:db8:ffff::1" => true; "ipv6 inside")]
+    #[test_case("2001:db8::/32", "2001:db9::" => false; "ipv6 outside")]
+    #[test_case("0.0.0.0/0", "::" => false; "family mismatch")]
+    fn contains(cidr: &str, address: &str) -> bool {
+        cidr.parse::<Cidr>()
+            .unwrap()
+            .contains(address.parse().unwrap())
+    }
+
+    #[test_case("192.0.2.200", 25 => Some("192.0.2.128/25".to_string()); "ipv4")]
+    #[test_case("192.0.2.200", 32 => Some("192.0.2.200/32".to_string()); "ipv4 address")]
+    #[test_case("192.0.2.200", 0 => Some("0.0.0.0/0".to_string()); "ipv4 everything")]
+    #[test_case("2001:db8:1:2::1", 48 => Some("2001:db8:1::/48".to_string()); "ipv6")]
+    #[test_case("2001:db8:1:2::1", 128 => Some("2001:db8:1:2::1/128".to_string()); "ipv6 address")]
+    #[test_case("192.0.2.200", 33 => None; "prefix too long")]
+    fn containing(address: &str, prefix: u8) -> Option<String> {
+        Cidr::containing(address.parse().unwrap(), prefix).map(|cidr| cidr.to_string())
+    }
+
+    #[test]
+    fn contains_subnet() {
+        let cidr = "10.0.0.0/8".parse::<Cidr>().unwrap();
+
+        assert!(cidr.contains_subnet("10.1.0.0".parse().unwrap(), 16));
+        assert!(!cidr.contains_subnet("10.0.0.0".parse().unwrap(), 4));
+    }
+}
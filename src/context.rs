@@ -1,23 +1,36 @@
 use std::{
     collections::{HashMap, HashSet},
     env,
+    error::Error,
+    fmt::{self, Display, Formatter},
     fs::File,
-    io::Read,
+    io::{self, Read},
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     os::unix::prelude::OsStringExt,
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, OnceLock, RwLock},
     time::Duration,
 };
 
-use log::LevelFilter;
+use log::{error, info, warn, LevelFilter};
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use serde::Deserialize;
 use serde_default::DefaultFromSerde;
 use serde_with::{hex::Hex, serde_as, BytesOrString, DurationSecondsWithFrac};
 
-use crate::{node::Node, question::Question, zone::read_zone};
+use crate::{
+    cidr::Cidr,
+    node::Node,
+    opt::client_subnet::ClientSubnetOpt,
+    question::Question,
+    region::RegionDatabase,
+    text::DomainName,
+    validate::{validate_zone, Severity, ValidationIssue, ValidationIssueKind},
+    view::{View, ViewTable},
+    zone::{read_zone, Zone, ZoneError, ZoneTable},
+    zonemd::{self, ZonemdError},
+};
 
 const fn default_true() -> bool {
     true
@@ -42,15 +55,29 @@ const fn default_log_level() -> LevelFilter {
 }
 
 fn default_secret() -> [u8; 16] {
-    let mut secret = [0; 16];
-    ChaCha20Rng::from_entropy().fill_bytes(&mut secret);
-    secret
+    // Only generated once, so that reloading the config doesn't invalidate issued cookies.
+    static SECRET: OnceLock<[u8; 16]> = OnceLock::new();
+
+    *SECRET.get_or_init(|| {
+        let mut secret = [0; 16];
+        ChaCha20Rng::from_entropy().fill_bytes(&mut secret);
+        secret
+    })
 }
 
 fn default_identity() -> Vec<u8> {
     gethostname::gethostname().into_vec()
 }
 
+const fn default_padding_block_size() -> u16 {
+    // The block size recommended for responses by RFC 8467
+    468
+}
+
+fn default_version() -> String {
+    format!("realm {}", env!("CARGO_PKG_VERSION"))
+}
+
 const fn default_keepalive() -> Duration {
     Duration::from_secs(300)
 }
@@ -97,6 +124,13 @@ pub struct ServerConfig {
     #[serde(default)]
     pub cookie_strategy: CookieStrategy,
 
+    #[serde(default)]
+    pub any_policy: AnyPolicy,
+
+    /// Responses to padded queries are padded to a multiple of this size, zero disables padding
+    #[serde(default = "default_padding_block_size")]
+    pub padding_block_size: u16,
+
     #[serde(default = "default_true")]
     pub identity_enabled: bool,
     #[serde_as(as = "BytesOrString")]
@@ -117,12 +151,72 @@ pub enum CookieStrategy {
     Enforce,
 }
 
+/// How ANY queries are answered, see RFC 8482
+#[derive(Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnyPolicy {
+    /// Answer with a single synthesised HINFO record
+    #[default]
+    Hinfo,
+    /// Answer with one of the RRsets at the name
+    Rrset,
+    /// Answer with every RRset over TCP, and set the TC bit over UDP so the client retries
+    Tcp,
+}
+
 #[serde_as]
-#[derive(Deserialize, DefaultFromSerde)]
+#[derive(Debug, Clone, Deserialize, DefaultFromSerde)]
 #[serde(deny_unknown_fields)]
 pub struct ZoneConfig {
     #[serde(default)]
     pub file: Option<PathBuf>,
+    /// Refuse to load the zone when none of its ZONEMD records match its contents, rather than
+    /// warning about it
+    #[serde(default)]
+    pub zonemd_strict: bool,
+    /// Add or refresh a SHA-384 ZONEMD record at the apex whenever the zone is loaded
+    #[serde(default)]
+    pub zonemd_generate: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ViewConfig {
+    pub name: String,
+    /// Client addresses that select this view
+    #[serde(default)]
+    pub networks: Vec<Cidr>,
+    /// Subnets from the client subnet option that select this view. There's no TSIG key
+    /// criterion, as TSIG signatures aren't verified.
+    #[serde(default)]
+    pub client_subnets: Vec<Cidr>,
+    #[serde(default)]
+    pub zones: HashMap<String, ZoneConfig>,
+}
+
+/// The CH TXT names answered with details about the server, `hostname.bind` and `id.server` are
+/// answered with the identity name and refused when it's disabled
+#[derive(Deserialize, DefaultFromSerde)]
+#[serde(deny_unknown_fields)]
+pub struct ChaosConfig {
+    #[serde(default = "default_true")]
+    pub version_bind: bool,
+    #[serde(default = "default_true")]
+    pub version_server: bool,
+    #[serde(default = "default_true")]
+    pub hostname_bind: bool,
+    #[serde(default = "default_true")]
+    pub id_server: bool,
+    #[serde(default = "default_version")]
+    pub version: String,
+}
+
+#[derive(Deserialize, DefaultFromSerde)]
+#[serde(deny_unknown_fields)]
+pub struct GeoConfig {
+    /// A CSV file mapping networks to regions, every client is in the default region without one
+    #[serde(default)]
+    pub database: Option<PathBuf>,
 }
 
 #[derive(Deserialize, DefaultFromSerde)]
@@ -134,86 +228,357 @@ pub struct Config {
     pub server: ServerConfig,
     #[serde(default)]
     pub zones: HashMap<String, ZoneConfig>,
+    #[serde(default)]
+    pub views: Vec<ViewConfig>,
+    #[serde(default)]
+    pub geo: GeoConfig,
+    #[serde(default)]
+    pub chaos: ChaosConfig,
 }
 
-pub struct ServerContext {
-    pub config: Arc<Config>,
-    pub root: Node,
+impl ZoneConfig {
+    /// The location of the zone file, defaulting to `zones/<origin>zone`
+    pub fn path(&self, origin: &DomainName) -> PathBuf {
+        self.file
+            .clone()
+            .unwrap_or_else(|| Path::new("zones").join(format!("{}zone", origin)))
+    }
 }
 
-impl ServerContext {
-    pub fn from_env() -> Self {
-        let config_path = env::var("CONFIG_FILE").unwrap_or_else(|_| "realm.yml".to_string());
-        let config_path = Path::new(config_path.as_str());
-
-        let config_file = File::open(config_path).unwrap_or_else(|err| {
-            eprintln!("Couldn't open config file at {:?}", config_path);
-            eprintln!("Tip: use the CONFIG_FILE environment variable to specify a file location.");
-            panic!("{}", err);
-        });
-        let config = serde_yaml::from_reader::<_, Config>(config_file)
-            .unwrap_or_else(|err| panic!("Error parsing config: {}", err));
+impl Config {
+    /// Reads and deserialises the config file at a given path
+    pub fn read(path: &Path) -> Result<Self, LoadError> {
+        let file = File::open(path).map_err(|err| LoadError::ConfigIo(path.to_path_buf(), err))?;
+
+        serde_yaml::from_reader(file).map_err(|err| LoadError::Config(path.to_path_buf(), err))
+    }
+}
+
+/// The location of the config file, which can be set with the `CONFIG_FILE` environment variable
+pub fn config_path() -> PathBuf {
+    env::var_os("CONFIG_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("realm.yml"))
+}
 
-        let mut root = Node::new();
+/// Parses the name of a zone, the trailing dot is optional
+pub fn parse_origin(name: &str) -> Result<DomainName, LoadError> {
+    let mut name = name.to_string();
+    if !name.ends_with('.') {
+        name.push('.')
+    }
+
+    name.parse().map_err(|_| LoadError::Origin(name))
+}
+
+/// Reads the region database set in the config, or an empty one if none is set
+pub fn load_regions(config: &GeoConfig) -> Result<RegionDatabase, LoadError> {
+    let Some(path) = &config.database else {
+        return Ok(RegionDatabase::new());
+    };
+
+    let mut source = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut source))
+        .map_err(|err| LoadError::RegionsIo(path.to_path_buf(), err))?;
+
+    RegionDatabase::read(&source).map_err(|line| LoadError::Regions(path.to_path_buf(), line))
+}
+
+/// Reads and parses a zone file
+pub fn read_zone_file(path: &Path, origin: &DomainName) -> Result<Node, LoadError> {
+    let mut source = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut source))
+        .map_err(|err| LoadError::ZoneIo(path.to_path_buf(), err))?;
+
+    read_zone(&source, origin.clone()).map_err(|errors| LoadError::Zone {
+        path: path.to_path_buf(),
+        source,
+        errors,
+    })
+}
+
+/// Reads, parses and validates a zone file. Validation warnings are returned alongside the zone.
+pub fn load_zone(
+    path: &Path,
+    origin: &DomainName,
+    config: &ZoneConfig,
+) -> Result<(Node, Vec<ValidationIssue>), LoadError> {
+    let mut zone = read_zone_file(path, origin)?;
+
+    let issues = validate_zone(&zone, origin);
+    if issues
+        .iter()
+        .any(|issue| issue.severity() == Severity::Error)
+    {
+        return Err(LoadError::Validation(origin.clone(), issues));
+    }
+
+    if config.zonemd_strict
+        && issues
+            .iter()
+            .any(|issue| *issue.kind() == ValidationIssueKind::ZonemdMismatch)
+    {
+        return Err(LoadError::Zonemd(origin.clone()));
+    }
+
+    if config.zonemd_generate {
+        zonemd::refresh(&mut zone, origin)
+            .map_err(|err| LoadError::ZonemdGenerate(origin.clone(), err))?;
+    }
+
+    Ok((zone, issues))
+}
 
-        for (name, zone) in &config.zones {
-            let mut name = name.to_string();
-            if !name.ends_with('.') {
-                name.push('.')
+#[derive(Debug)]
+pub enum LoadError {
+    ConfigIo(PathBuf, io::Error),
+    Config(PathBuf, serde_yaml::Error),
+    Origin(String),
+    ZoneIo(PathBuf, io::Error),
+    Zone {
+        path: PathBuf,
+        source: String,
+        errors: Vec<ZoneError>,
+    },
+    Validation(DomainName, Vec<ValidationIssue>),
+    Zonemd(DomainName),
+    ZonemdGenerate(DomainName, ZonemdError),
+    RegionsIo(PathBuf, io::Error),
+    Regions(PathBuf, usize),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::ConfigIo(path, err) => {
+                writeln!(f, "Couldn't open config file at {:?}: {}", path, err)?;
+                write!(
+                    f,
+                    "Tip: use the CONFIG_FILE environment variable to specify a file location.",
+                )
+            }
+            Self::Config(path, err) => write!(f, "Error parsing config at {:?}: {}", path, err),
+            Self::Origin(name) => write!(f, "{:?} is not a valid origin", name),
+            Self::ZoneIo(path, err) => {
+                writeln!(f, "Couldn't open zone file at {:?}: {}", path, err)?;
+                write!(
+                    f,
+                    "Tip: use the `file` directive to specify a file location."
+                )
+            }
+            Self::Zone {
+                path,
+                source,
+                errors,
+            } => {
+                for err in errors {
+                    writeln!(f, "{}\n", err.diagnostic(path, source))?;
+                }
+                write!(f, "Couldn't parse zone file at {:?}", path)
+            }
+            Self::Validation(origin, issues) => {
+                for issue in issues {
+                    writeln!(f, "{}", issue)?;
+                }
+                write!(f, "Zone {} failed validation", origin)
             }
+            Self::Zonemd(origin) => write!(
+                f,
+                "Zone {} doesn't match its ZONEMD digest, refusing to load it",
+                origin
+            ),
+            Self::ZonemdGenerate(origin, err) => {
+                write!(
+                    f,
+                    "Couldn't generate a ZONEMD record for zone {}: {}",
+                    origin, err
+                )
+            }
+            Self::RegionsIo(path, err) => {
+                write!(f, "Couldn't open region database at {:?}: {}", path, err)
+            }
+            Self::Regions(path, line) => write!(
+                f,
+                "Error parsing region database at {:?}: invalid network on line {}",
+                path, line
+            ),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Reads every zone in a set of zone configs, failing on the first zone that doesn't load
+fn load_zones(configs: &HashMap<String, ZoneConfig>) -> Result<ZoneTable, LoadError> {
+    let mut zones = ZoneTable::new();
+
+    for (name, zone_config) in configs {
+        let origin = parse_origin(name)?;
+        let (root, issues) = load_zone(&zone_config.path(&origin), &origin, zone_config)?;
+        for issue in &issues {
+            eprintln!("{}", issue);
+        }
+
+        zones.insert(Zone::new(origin, root, zone_config.clone()));
+    }
 
-            let origin = name
-                .parse()
-                .unwrap_or_else(|_| panic!("{:?} is not a valid origin", name));
-
-            let mut zone_file = zone
-                .file
-                .as_deref()
-                .map(|path| {
-                    File::open(path).unwrap_or_else(|err| {
-                        eprintln!("Couldn't open zone file at {:?}", path);
-                        panic!("{}", err);
-                    })
-                })
-                .unwrap_or_else(|| {
-                    let path = Path::new("zones").join(name + "zone");
-                    File::open(&path).unwrap_or_else(|err| {
-                        eprintln!("Couldn't open zone file at {:?}", path);
-                        eprintln!("Tip: use the `file` directive to specify a file location.");
-                        panic!("{}", err);
-                    })
-                });
-
-            let mut zone_buf = String::with_capacity(zone_file.metadata().unwrap().len() as usize);
-            zone_file
-                .read_to_string(&mut zone_buf)
-                .expect("Error reading zone");
-            match read_zone(&zone_buf, origin) {
-                Ok(zone) => root.merge(zone),
-                Err(err) => panic!("Couldn't parse zone file: {:?}", err),
+    Ok(zones)
+}
+
+/// Reads every zone in a set of zone configs again, zones that fail to load keep their version
+/// from a previous table
+fn reload_zones(configs: &HashMap<String, ZoneConfig>, previous: Option<&ZoneTable>) -> ZoneTable {
+    let mut zones = ZoneTable::new();
+
+    for (name, zone_config) in configs {
+        let origin = match parse_origin(name) {
+            Ok(origin) => origin,
+            Err(err) => {
+                error!("{}", err);
+                continue;
+            }
+        };
+
+        match load_zone(&zone_config.path(&origin), &origin, zone_config) {
+            Ok((root, issues)) => {
+                for issue in &issues {
+                    warn!("{}", issue);
+                }
+                info!("Loaded zone {}", origin);
+
+                zones.insert(Zone::new(origin, root, zone_config.clone()));
             }
+            Err(err) => {
+                error!("Keeping previous version of zone {}:\n{}", origin, err);
+
+                if let Some(zone) = previous.and_then(|previous| previous.get(&origin)) {
+                    zones.insert(zone.clone());
+                }
+            }
+        }
+    }
+
+    zones
+}
+
+/// The config, views and region database being served, which are swapped in together so that a
+/// query never sees parts of two different versions
+pub struct Snapshot {
+    pub config: Arc<Config>,
+    pub views: Arc<ViewTable>,
+    pub regions: Arc<RegionDatabase>,
+}
+
+pub struct ServerContext {
+    snapshot: RwLock<Arc<Snapshot>>,
+}
+
+impl ServerContext {
+    /// Reads the config file and every zone it references
+    pub fn from_env() -> Result<Self, LoadError> {
+        Self::from_config(Config::read(&config_path())?)
+    }
+
+    /// Reads every zone a config references
+    pub fn from_config(config: Config) -> Result<Self, LoadError> {
+        let mut views = ViewTable::new(load_zones(&config.zones)?);
+        for view_config in &config.views {
+            views.push(View::new(view_config, load_zones(&view_config.zones)?));
         }
 
+        let regions = load_regions(&config.geo)?;
+
+        Ok(Self::new(config, views, regions))
+    }
+
+    /// Constructs a context serving zones that have already been loaded
+    pub fn new(config: Config, views: ViewTable, regions: RegionDatabase) -> Self {
         Self {
-            config: Arc::from(config),
-            root,
+            snapshot: RwLock::new(Arc::new(Snapshot {
+                config: Arc::new(config),
+                views: Arc::new(views),
+                regions: Arc::new(regions),
+            })),
         }
     }
+
+    /// The config, views and region database currently being served
+    pub fn snapshot(&self) -> Arc<Snapshot> {
+        Arc::clone(&self.snapshot.read().unwrap())
+    }
+
+    /// Reads the config file, every zone and the region database again, then swaps them in at once.
+    /// Zones and region databases that fail to load keep serving their previous version. Listeners
+    /// and the log level are only set up at startup, so changes to them require a restart.
+    pub fn reload(&self) {
+        self.reload_from(&config_path())
+    }
+
+    fn reload_from(&self, path: &Path) {
+        let previous = self.snapshot();
+
+        let config = match Config::read(path) {
+            Ok(config) => Arc::new(config),
+            Err(err) => {
+                error!("Keeping previous config:\n{}", err);
+                Arc::clone(&previous.config)
+            }
+        };
+
+        let mut views = ViewTable::new(reload_zones(
+            &config.zones,
+            Some(previous.views.default_zones()),
+        ));
+        for view_config in &config.views {
+            let previous = previous.views.get(&view_config.name).map(View::zones);
+            views.push(View::new(
+                view_config,
+                reload_zones(&view_config.zones, previous),
+            ));
+        }
+
+        let regions = match load_regions(&config.geo) {
+            Ok(regions) => Arc::new(regions),
+            Err(err) => {
+                error!("Keeping previous region database:\n{}", err);
+                Arc::clone(&previous.regions)
+            }
+        };
+
+        *self.snapshot.write().unwrap() = Arc::new(Snapshot {
+            config,
+            views: Arc::new(views),
+            regions,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    Udp,
+    Tcp,
 }
 
 pub struct ConnectionContext {
-    pub config: Arc<Config>,
     pub server: Arc<ServerContext>,
     pub addr: SocketAddr,
+    pub transport: Transport,
     pub keepalive: Duration,
 }
 
 impl ConnectionContext {
-    pub fn new(server: Arc<ServerContext>, addr: SocketAddr, keepalive: Duration) -> Self {
+    pub fn new(
+        server: Arc<ServerContext>,
+        addr: SocketAddr,
+        transport: Transport,
+        keepalive: Duration,
+    ) -> Self {
         Self {
-            config: Arc::clone(&server.config),
             server,
             addr,
+            transport,
             keepalive,
         }
     }
@@ -221,19 +586,136 @@ impl ConnectionContext {
 
 pub struct QueryContext {
     pub config: Arc<Config>,
+    pub views: Arc<ViewTable>,
+    pub regions: Arc<RegionDatabase>,
     pub server: Arc<ServerContext>,
     pub connection: Arc<Mutex<ConnectionContext>>,
     pub resolved: HashSet<Question>,
+    /// The client subnet given in the query, if any
+    pub client_subnet: Option<ClientSubnetOpt>,
+    /// The scope prefix to echo back with the client subnet, narrowed by answers that depend on it
+    pub client_subnet_scope: u8,
+    /// Whether the query asked for the expire time of the zone with an EDNS expire option
+    pub expire_requested: bool,
 }
 
 impl QueryContext {
+    /// Constructs a context for a query, which is answered from the snapshot being served when
+    /// it arrives even if a reload finishes in the meantime
     pub fn new(connection: Arc<Mutex<ConnectionContext>>) -> Self {
         let server = Arc::clone(&connection.lock().unwrap().server);
+        let snapshot = server.snapshot();
         Self {
-            config: Arc::clone(&server.config),
+            config: Arc::clone(&snapshot.config),
+            views: Arc::clone(&snapshot.views),
+            regions: Arc::clone(&snapshot.regions),
             server,
             connection: Arc::clone(&connection),
             resolved: HashSet::new(),
+            client_subnet: None,
+            client_subnet_scope: 0,
+            expire_requested: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::record::{RecordClass, RecordType};
+
+    const SOA: &str = "@ 3600 IN SOA ns admin 1 7200 3600 1209600 3600\n";
+
+    /// The addresses of www.example. in the default zones of a server
+    fn addresses(context: &ServerContext) -> Vec<String> {
+        let snapshot = context.snapshot();
+        let zone = snapshot
+            .views
+            .default_zones()
+            .get(&"example.".parse().unwrap())
+            .unwrap();
+
+        zone.find(&"www.example.".parse::<DomainName>().unwrap())
+            .map(|node| node.resource_record_set(RecordClass::In, RecordType::A))
+            .unwrap_or_default()
+            .iter()
+            .map(|record| record.display_data().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn load_regions_fixture() {
+        let database = load_regions(&GeoConfig {
+            database: Some(
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/geolite2-blocks.csv"),
+            ),
+        })
+        .unwrap();
+
+        let region = |addr: &str| {
+            database
+                .select(addr.parse().unwrap(), None)
+                .0
+                .map(str::to_string)
+        };
+        assert_eq!(region("192.0.2.1").as_deref(), Some("2635167"));
+        assert_eq!(region("192.0.2.200").as_deref(), Some("6252001"));
+        assert_eq!(region("198.51.100.1"), None);
+        assert_eq!(region("2001:db8:1::1").as_deref(), Some("2635167"));
+        assert_eq!(region("2001:db8:2::1").as_deref(), Some("1861060"));
+
+        let client_subnet = ClientSubnetOpt::new("203.0.113.0".parse().unwrap(), 24, 0);
+        assert_eq!(
+            database.select("192.0.2.1".parse().unwrap(), Some(&client_subnet)),
+            (Some("2921044"), 24),
+        );
+    }
+
+    #[test]
+    fn load_regions_missing() {
+        let err = load_regions(&GeoConfig {
+            database: Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/missing.csv")),
+        })
+        .unwrap_err();
+
+        assert!(matches!(err, LoadError::RegionsIo(_, _)));
+    }
+
+    #[test]
+    fn reload_keeps_broken_zone() {
+        let dir = env::temp_dir().join(format!("realm-{}-reload", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let zone_path = dir.join("example.zone");
+        let config_path = dir.join("realm.yml");
+        fs::write(
+            &config_path,
+            format!("zones:\n  example:\n    file: {:?}\n", zone_path),
+        )
+        .unwrap();
+
+        fs::write(&zone_path, format!("{}www 300 IN A 192.0.2.1\n", SOA)).unwrap();
+        let context = ServerContext::from_config(Config::read(&config_path).unwrap()).unwrap();
+        assert_eq!(addresses(&context), vec!["192.0.2.1"]);
+
+        fs::write(&zone_path, format!("{}www 300 IN A 192.0.2.2\n", SOA)).unwrap();
+        context.reload_from(&config_path);
+        assert_eq!(addresses(&context), vec!["192.0.2.2"]);
+
+        fs::write(&zone_path, format!("{}www 300 IN A bad\n", SOA)).unwrap();
+        context.reload_from(&config_path);
+        assert_eq!(addresses(&context), vec!["192.0.2.2"]);
+
+        fs::write(
+            &zone_path,
+            format!("{}www 300 IN CNAME host\nwww 300 IN A 192.0.2.3\n", SOA),
+        )
+        .unwrap();
+        context.reload_from(&config_path);
+        assert_eq!(addresses(&context), vec!["192.0.2.2"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
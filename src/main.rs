@@ -1,4 +1,4 @@
-use std::{io, sync::Arc};
+use std::{env, fs, io, iter, path::Path, process, sync::Arc};
 
 use chrono::{
     format::{Fixed, Item, Numeric, Pad},
@@ -7,43 +7,114 @@ use chrono::{
 use colored::Colorize;
 use fern::Dispatch;
 use futures::future;
-use log::{Level, LevelFilter};
+use log::{error, info, Level, LevelFilter};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    task,
+};
 
 use crate::{
-    context::ServerContext,
+    context::{
+        config_path, load_regions, load_zone, parse_origin, read_zone_file, Config, LoadError,
+        ServerContext, ZoneConfig,
+    },
+    node::Node,
     server::{TcpDnsServer, UdpDnsServer},
+    text::DomainName,
+    validate::ValidationIssue,
+    zone::{unwritten_lines, write_zone},
 };
 
 pub mod bitfield;
+pub mod cidr;
 pub mod context;
 pub mod message;
 pub mod node;
 pub mod opt;
 pub mod question;
 pub mod record;
+pub mod region;
 pub mod resolver;
 pub mod serial;
 pub mod server;
 pub mod text;
+pub mod validate;
+pub mod view;
 pub mod wire;
 pub mod zone;
+pub mod zonemd;
+
+const USAGE: &str = "\
+Usage: realm [COMMAND]
+
+Commands:
+  serve                       Start the server, this is the default
+  check-config                Check the config file and every zone it references
+  check-zone <origin> <file>  Check a single zone file
+  fmt [--zonemd] [--stdout] <origin> <file>
+                              Rewrite a zone file in canonical form, adding or
+                              refreshing its ZONEMD record with --zonemd, or
+                              print it instead with --stdout";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let code = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] | ["serve"] => serve(),
+        ["check-config"] => check_config(),
+        ["check-zone", origin, file] => check_zone(origin, Path::new(file)),
+        ["fmt", ref args @ ..] => format_command(args),
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    };
+
+    process::exit(code);
+}
 
 #[tokio::main]
-async fn main() {
-    let context = Arc::new(ServerContext::from_env());
+async fn serve() -> i32 {
+    let context = match ServerContext::from_env() {
+        Ok(context) => Arc::new(context),
+        Err(err) => {
+            eprintln!("{}", err);
+            return 1;
+        }
+    };
+
+    let config = Arc::clone(&context.snapshot().config);
+
+    init_logger(config.log.level);
 
-    init_logger(context.config.log.level);
+    let reload_context = Arc::clone(&context);
+    tokio::spawn(async move {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                error!("Couldn't listen for SIGHUP, reloading is disabled: {}", err);
+                return;
+            }
+        };
+
+        while hangup.recv().await.is_some() {
+            info!("Received SIGHUP, reloading config and zones");
+
+            let context = Arc::clone(&reload_context);
+            _ = task::spawn_blocking(move || context.reload()).await;
+        }
+    });
 
     let mut handles = Vec::new();
 
-    if context.config.server.udp_enabled {
+    if config.server.udp_enabled {
         let context = Arc::clone(&context);
         handles.push(tokio::spawn(async {
             UdpDnsServer::new(context).run().await.unwrap();
         }));
     }
 
-    if context.config.server.tcp_enabled {
+    if config.server.tcp_enabled {
         let context = Arc::clone(&context);
         handles.push(tokio::spawn(async {
             TcpDnsServer::new(context).run().await.unwrap();
@@ -51,6 +122,148 @@ async fn main() {
     }
 
     future::join_all(handles).await;
+
+    0
+}
+
+fn check_config() -> i32 {
+    let config = match Config::read(&config_path()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            return 1;
+        }
+    };
+
+    let zone_configs = iter::once(&config.zones).chain(config.views.iter().map(|view| &view.zones));
+
+    let mut code = 0;
+    if let Err(err) = load_regions(&config.geo) {
+        eprintln!("{}", err);
+        code = 1;
+    }
+
+    for (name, zone) in zone_configs.flatten() {
+        code = code.max(match parse_origin(name) {
+            Ok(origin) => report_zone(&origin, load_zone(&zone.path(&origin), &origin, zone)),
+            Err(err) => {
+                eprintln!("{}", err);
+                1
+            }
+        });
+    }
+
+    code
+}
+
+fn check_zone(origin: &str, path: &Path) -> i32 {
+    match parse_origin(origin) {
+        Ok(origin) => report_zone(&origin, load_zone(path, &origin, &ZoneConfig::default())),
+        Err(err) => {
+            eprintln!("{}", err);
+            1
+        }
+    }
+}
+
+/// Reads the flags given to `fmt`, which come before the origin and file
+fn format_command(mut args: &[&str]) -> i32 {
+    let mut add_zonemd = false;
+    let mut to_stdout = false;
+
+    while let [flag, rest @ ..] = args {
+        match *flag {
+            "--zonemd" => add_zonemd = true,
+            "--stdout" => to_stdout = true,
+            _ => break,
+        }
+        args = rest;
+    }
+
+    match args {
+        [origin, file] => format_zone(origin, Path::new(file), add_zonemd, to_stdout),
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    }
+}
+
+fn format_zone(origin: &str, path: &Path, add_zonemd: bool, to_stdout: bool) -> i32 {
+    let result = parse_origin(origin).and_then(|origin| {
+        let mut zone = read_zone_file(path, &origin)?;
+        if add_zonemd {
+            zonemd::refresh(&mut zone, &origin)
+                .map_err(|err| LoadError::ZonemdGenerate(origin.clone(), err))?;
+        }
+
+        Ok((zone, origin))
+    });
+
+    let formatted = match result {
+        Ok((zone, origin)) => match write_zone(&zone, &origin) {
+            Ok(formatted) => formatted,
+            Err(err) => {
+                eprintln!("Couldn't write zone {}: {}", origin, err);
+                return 1;
+            }
+        },
+        Err(err) => {
+            eprintln!("{}", err);
+            return 1;
+        }
+    };
+
+    if to_stdout {
+        print!("{}", formatted);
+        return 0;
+    }
+
+    let unwritten = match fs::read_to_string(path) {
+        Ok(source) => unwritten_lines(&source),
+        Err(err) => {
+            eprintln!("Couldn't open zone file at {:?}: {}", path, err);
+            return 1;
+        }
+    };
+    if let Some(first) = unwritten.first() {
+        eprintln!(
+            "warning: rewriting {:?} drops the comments and $ORIGIN/$TTL entries on {} lines, \
+             starting at line {}",
+            path,
+            unwritten.len(),
+            first,
+        );
+    }
+
+    match fs::write(path, formatted) {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("Couldn't write zone file at {:?}: {}", path, err);
+            1
+        }
+    }
+}
+
+/// Prints the outcome of loading a zone, returning the exit code for it
+fn report_zone(
+    origin: &DomainName,
+    result: Result<(Node, Vec<ValidationIssue>), LoadError>,
+) -> i32 {
+    match result {
+        Ok((_, issues)) => {
+            for issue in &issues {
+                eprintln!("{}", issue);
+            }
+            println!("zone {}: OK", origin);
+            0
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            println!("zone {}: FAILED", origin);
+            1
+        }
+    }
 }
 
 fn init_logger(filter: LevelFilter) {
@@ -91,3 +304,85 @@ fn init_logger(filter: LevelFilter) {
         .apply()
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    const SOA: &str = "@ 3600 IN SOA ns admin 1 7200 3600 1209600 3600\n";
+
+    /// Writes a zone file to a path unique to the test
+    fn zone_file(name: &str, source: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("realm-{}-{}.zone", process::id(), name));
+        fs::write(&path, source).unwrap();
+        path
+    }
+
+    #[test]
+    fn check_zone_valid() {
+        let path = zone_file("check-valid", &format!("{}www 300 IN A 192.0.2.1\n", SOA));
+
+        assert_eq!(check_zone("example", &path), 0);
+    }
+
+    #[test]
+    fn check_zone_warnings() {
+        let path = zone_file(
+            "check-warnings",
+            &format!("{}www 300 IN A 192.0.2.1\nwww 60 IN A 192.0.2.2\n", SOA),
+        );
+
+        assert_eq!(check_zone("example", &path), 0);
+    }
+
+    #[test]
+    fn check_zone_syntax_error() {
+        let path = zone_file("check-syntax", &format!("{}www 300 IN A bad\n", SOA));
+
+        assert_eq!(check_zone("example", &path), 1);
+
+        let err = load_zone(
+            &path,
+            &parse_origin("example").unwrap(),
+            &ZoneConfig::default(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains(&format!("{}:2:14: bad zone entry", path.display())));
+    }
+
+    #[test]
+    fn check_zone_validation_error() {
+        let path = zone_file(
+            "check-validation",
+            &format!("{}www 300 IN CNAME host\nwww 300 IN A 192.0.2.1\n", SOA),
+        );
+
+        assert_eq!(check_zone("example", &path), 1);
+
+        let err = load_zone(
+            &path,
+            &parse_origin("example").unwrap(),
+            &ZoneConfig::default(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("error: www.example.: CNAME and other data"));
+    }
+
+    #[test]
+    fn check_zone_missing_file() {
+        let path = env::temp_dir().join(format!("realm-{}-missing.zone", process::id()));
+
+        assert_eq!(check_zone("example", &path), 1);
+    }
+
+    #[test]
+    fn check_zone_bad_origin() {
+        let path = zone_file("check-origin", SOA);
+
+        assert_eq!(check_zone("bad..origin", &path), 1);
+    }
+}
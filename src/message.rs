@@ -8,7 +8,11 @@ use paste::paste;
 
 use crate::{
     bitfield::{BitField, BitFieldAssign},
-    opt::Opt,
+    opt::{
+        extended_error::{ExtendedErrorCode, ExtendedErrorOpt},
+        padding::PaddingOpt,
+        Opt,
+    },
     question::Question,
     record::{opt::OptRecord, Record, RecordData, RecordType},
     text::DomainName,
@@ -237,6 +241,67 @@ impl Message {
     getter_adder_impl!(authorities, authority, Record);
     getter_adder_impl!(additionals, additional, Record);
 
+    /// Constructs a response with a given response code, echoing the header of a raw message
+    /// that couldn't be handled. Returns `None` if the message is too short to have a header.
+    pub fn error_response(packet: &[u8], response_code: ResponseCode) -> Option<Self> {
+        if packet.len() < 12 {
+            return None;
+        }
+
+        let mut reader = WireRead::new(packet);
+        let id = u16::decode(&mut reader).ok()?;
+        let flags = u16::decode(&mut reader).ok()?;
+
+        let mut response = Self::new(id);
+        response
+            .set_packet_type(PacketType::Response)
+            .set_opcode((flags.get_bits(Self::BITS_OPCODE) as u8).into())
+            .set_recursion_desired(flags.get_flag(Self::FLAG_RECURSION_DESIRED))
+            .set_response_code(response_code);
+
+        Some(response)
+    }
+
+    /// Adds an extended DNS error option, unless this is not an EDNS message and the option
+    /// can't be carried
+    pub fn add_extended_error(
+        &mut self,
+        info_code: ExtendedErrorCode,
+        extra_text: &str,
+    ) -> &mut Self {
+        if self.edns_version.is_some() {
+            self.options
+                .push(ExtendedErrorOpt::new(info_code, extra_text).into());
+        }
+
+        self
+    }
+
+    /// Grows the padding option of this message, if it has one, so that the encoded message is a
+    /// multiple of `block_size` bytes without going over `max_size`. Must be done once the message
+    /// is otherwise final, after truncating it.
+    pub fn pad_to_block(&mut self, block_size: usize, max_size: usize) {
+        if block_size == 0 || self.edns_version.is_none() {
+            return;
+        }
+
+        let Some(index) = self
+            .options
+            .iter()
+            .position(|option| matches!(option, Opt::Padding(_)))
+        else {
+            return;
+        };
+
+        self.options[index] = PaddingOpt::new(&[]).into();
+        let unpadded_size = self.size();
+        let padded_size = (unpadded_size.div_ceil(block_size) * block_size)
+            .min(max_size)
+            .max(unpadded_size);
+
+        self.options[index] = PaddingOpt::new(&vec![0; padded_size - unpadded_size]).into();
+    }
+
     pub fn truncate_to(&mut self, size: usize) {
         let mut size = size as isize - 12;
 
@@ -494,3 +559,59 @@ impl Display for Message {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::wire::to_wire;
+
+    #[test_case(&[]; "empty")]
+    #[test_case(&[0xbe, 0xef]; "id only")]
+    #[test_case(&[0xbe, 0xef, 0, 0, 0, 1, 0, 0, 0, 0, 0]; "short header")]
+    fn error_response_short(packet: &[u8]) {
+        assert_eq!(
+            Message::error_response(packet, ResponseCode::FormatError),
+            None,
+        );
+    }
+
+    #[test]
+    fn error_response_echo() {
+        let packet = [0xbe, 0xef, 0b0010_0001, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0xff];
+        let response = Message::error_response(&packet, ResponseCode::FormatError).unwrap();
+
+        assert_eq!(response.id(), 0xbeef);
+        assert_eq!(response.packet_type(), PacketType::Response);
+        assert_eq!(response.opcode(), Opcode::Notify);
+        assert!(response.recursion_desired());
+        assert_eq!(response.response_code(), ResponseCode::FormatError);
+        assert!(response.questions().is_empty());
+    }
+
+    #[test_case(None => 0; "no edns")]
+    #[test_case(Some(0) => 1; "edns")]
+    fn add_extended_error(edns_version: Option<u8>) -> usize {
+        let mut message = Message::new(0);
+        message
+            .set_edns_version(edns_version)
+            .add_extended_error(ExtendedErrorCode::NotAuthoritative, "");
+
+        message.options().len()
+    }
+
+    #[test_case(468, 65535 => 468; "block")]
+    #[test_case(468, 400 => 400; "limited")]
+    #[test_case(468, 12 => 27; "no room")]
+    #[test_case(0, 65535 => 27; "disabled")]
+    fn pad_to_block(block_size: usize, max_size: usize) -> usize {
+        let mut message = Message::new(0);
+        message
+            .set_edns_version(Some(0))
+            .add_option(PaddingOpt::new(&[]).into());
+        message.pad_to_block(block_size, max_size);
+
+        to_wire(&message).unwrap().len()
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::{
     record::{Record, RecordClass, RecordData, RecordType},
-    text::Label,
+    text::{Label, Name},
 };
 
 /// A node in the domain name space
@@ -10,6 +10,7 @@ use crate::{
 pub struct Node {
     children: HashMap<Label, Node>,
     records: HashMap<(RecordClass, RecordType), Vec<Record>>,
+    regions: HashMap<String, HashMap<(RecordClass, RecordType), Vec<Record>>>,
 }
 
 impl Node {
@@ -18,6 +19,7 @@ impl Node {
         Self {
             children: HashMap::new(),
             records: HashMap::new(),
+            regions: HashMap::new(),
         }
     }
 
@@ -26,9 +28,20 @@ impl Node {
         self.children.get(label)
     }
 
+    /// Gets a descendant node by walking the labels of a name from the current node
+    pub fn find<N>(&self, name: &N) -> Option<&Node>
+    where
+        N: Name,
+    {
+        name.labels()
+            .iter()
+            .rev()
+            .try_fold(self, |node, label| node.get(label))
+    }
+
     /// Gets a child node from the current node and inserts the node if it is not present
     pub fn insert(&mut self, label: Label) -> &mut Node {
-        self.children.entry(label).or_insert_with(Default::default)
+        self.children.entry(label).or_default()
     }
 
     /// Removes a child node from the current node and returns it if it existed
@@ -52,6 +65,38 @@ impl Node {
             .push(record)
     }
 
+    /// Retrieve the records for this node that are only served to clients in a region, `None` if
+    /// there are none for the region and the default records should be served instead
+    pub fn regional_record_set(
+        &self,
+        region: &str,
+        rclass: RecordClass,
+        rtype: RecordType,
+    ) -> Option<&[Record]> {
+        self.regions
+            .get(region)?
+            .get(&(rclass, rtype))
+            .map(|records| &records[..])
+            .filter(|records| !records.is_empty())
+    }
+
+    /// Whether any region has its own records for a given record class and record type
+    pub fn is_regional(&self, rclass: RecordClass, rtype: RecordType) -> bool {
+        self.regions
+            .values()
+            .any(|records| records.contains_key(&(rclass, rtype)))
+    }
+
+    /// Adds a record to this node that is only served to clients in a region
+    pub fn add_regional_record(&mut self, region: String, record: Record) {
+        self.regions
+            .entry(region)
+            .or_default()
+            .entry((record.rclass(), record.rtype()))
+            .or_default()
+            .push(record)
+    }
+
     /// The child nodes of this node
     pub fn children(&self) -> &HashMap<Label, Node> {
         &self.children
@@ -67,6 +112,12 @@ impl Node {
         &self.records
     }
 
+    /// The records associated with this node that are only served to clients in a region, by
+    /// region
+    pub fn regions(&self) -> &HashMap<String, HashMap<(RecordClass, RecordType), Vec<Record>>> {
+        &self.regions
+    }
+
     /// A mutable borrow to the records associated with this node
     pub fn records_mut(&mut self) -> &mut HashMap<(RecordClass, RecordType), Vec<Record>> {
         &mut self.records
@@ -77,7 +128,7 @@ impl Node {
         let mut nodes = vec![(Vec::<Label>::new(), other)];
 
         while let Some((insert_path, node)) = nodes.pop() {
-            if !node.records.is_empty() {
+            if !node.records.is_empty() || !node.regions.is_empty() {
                 let mut self_node = &mut *self;
                 for label in &insert_path {
                     self_node = self_node.insert(label.clone());
@@ -87,6 +138,13 @@ impl Node {
                         self_node.add_record(record);
                     }
                 }
+                for (region, record_sets) in node.regions {
+                    for (_, record_set) in record_sets {
+                        for record in record_set {
+                            self_node.add_regional_record(region.clone(), record);
+                        }
+                    }
+                }
             }
 
             for (label, child) in node.children {
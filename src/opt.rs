@@ -7,13 +7,17 @@ use crate::{
     context::QueryContext,
     message::Message,
     opt::{
-        cookie::CookieOpt, name_server_identifier::NameServerIdentifierOpt, other::OtherOpt,
-        padding::PaddingOpt, tcp_keepalive::TcpKeepaliveOpt,
+        client_subnet::ClientSubnetOpt, cookie::CookieOpt, expire::ExpireOpt,
+        extended_error::ExtendedErrorOpt, name_server_identifier::NameServerIdentifierOpt,
+        other::OtherOpt, padding::PaddingOpt, tcp_keepalive::TcpKeepaliveOpt,
     },
     wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
 };
 
+pub mod client_subnet;
 pub mod cookie;
+pub mod expire;
+pub mod extended_error;
 pub mod name_server_identifier;
 pub mod other;
 pub mod padding;
@@ -23,18 +27,24 @@ pub mod tcp_keepalive;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OptCode {
     NameServerIdentifier = 3,
+    ClientSubnet = 8,
+    Expire = 9,
     Cookie = 10,
     TcpKeepalive = 11,
     Padding = 12,
+    ExtendedError = 15,
 }
 
 impl Display for OptCode {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             Self::NameServerIdentifier => write!(f, "NSID"),
+            Self::ClientSubnet => write!(f, "CLIENT-SUBNET"),
+            Self::Expire => write!(f, "EXPIRE"),
             Self::Cookie => write!(f, "COOKIE"),
             Self::TcpKeepalive => write!(f, "tcp-keepalive"),
             Self::Padding => write!(f, "Padding"),
+            Self::ExtendedError => write!(f, "EDE"),
             Self::Other(code) => write!(f, "OPT{}", code),
         }
     }
@@ -43,9 +53,12 @@ impl Display for OptCode {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Opt {
     NameServerIdentifier(NameServerIdentifierOpt),
+    ClientSubnet(ClientSubnetOpt),
+    Expire(ExpireOpt),
     Cookie(CookieOpt),
     TcpKeepalive(TcpKeepaliveOpt),
     Padding(PaddingOpt),
+    ExtendedError(ExtendedErrorOpt),
     Other(OtherOpt),
 }
 
@@ -197,8 +210,11 @@ macro_rules! dns_opt_impl {
 
 dns_opt_impl! {
     NameServerIdentifier,
+    ClientSubnet,
+    Expire,
     Cookie,
     TcpKeepalive,
     Padding,
+    ExtendedError,
     _,
 }
@@ -0,0 +1,282 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use enum_other::other;
+
+use crate::{
+    context::QueryContext,
+    message::{Message, ResponseCode},
+    opt::{OptCode, OptData},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+};
+
+use super::OptHandleAction;
+
+/// An address family from the IANA address family numbers registry
+#[other(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFamily {
+    Ipv4 = 1,
+    Ipv6 = 2,
+}
+
+impl AddressFamily {
+    /// The length of an address of this family in bits
+    pub fn max_prefix(&self) -> Option<u8> {
+        match self {
+            Self::Ipv4 => Some(32),
+            Self::Ipv6 => Some(128),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+/// An edns-client-subnet option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientSubnetOpt {
+    family: AddressFamily,
+    source_prefix: u8,
+    scope_prefix: u8,
+    address: Vec<u8>,
+}
+
+impl ClientSubnetOpt {
+    /// Constructs a new edns-client-subnet option for an address truncated to a source prefix
+    pub fn new(address: IpAddr, source_prefix: u8, scope_prefix: u8) -> Self {
+        let (family, octets) = match address {
+            IpAddr::V4(ip) => (AddressFamily::Ipv4, ip.octets().to_vec()),
+            IpAddr::V6(ip) => (AddressFamily::Ipv6, ip.octets().to_vec()),
+        };
+        assert!(
+            source_prefix <= family.max_prefix().unwrap(),
+            "Source prefix must not be longer than the address",
+        );
+
+        let mut address = octets[..(source_prefix as usize).div_ceil(8)].to_vec();
+        if !source_prefix.is_multiple_of(8) {
+            *address.last_mut().unwrap() &= 0xff << (8 - source_prefix % 8);
+        }
+
+        Self {
+            family,
+            source_prefix,
+            scope_prefix,
+            address,
+        }
+    }
+
+    /// The address family of the subnet
+    pub fn family(&self) -> AddressFamily {
+        self.family
+    }
+
+    /// The number of leading bits of the address given by the client
+    pub fn source_prefix(&self) -> u8 {
+        self.source_prefix
+    }
+
+    /// The number of leading bits of the address the answer covers, must be zero in queries
+    pub fn scope_prefix(&self) -> u8 {
+        self.scope_prefix
+    }
+
+    /// The subnet address padded to a full address, `None` if the family or length is invalid
+    pub fn address(&self) -> Option<IpAddr> {
+        match self.family {
+            AddressFamily::Ipv4 => {
+                let mut octets = [0; 4];
+                octets
+                    .get_mut(..self.address.len())?
+                    .copy_from_slice(&self.address);
+                Some(IpAddr::V4(Ipv4Addr::from(octets)))
+            }
+            AddressFamily::Ipv6 => {
+                let mut octets = [0; 16];
+                octets
+                    .get_mut(..self.address.len())?
+                    .copy_from_slice(&self.address);
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            }
+            AddressFamily::Other(_) => None,
+        }
+    }
+
+    /// Checks the option is well formed for a query as described in RFC 7871 section 7.1.1
+    pub fn validate(&self) -> bool {
+        let Some(max_prefix) = self.family.max_prefix() else {
+            return false;
+        };
+
+        if self.source_prefix > max_prefix || self.scope_prefix != 0 {
+            return false;
+        }
+
+        // The address is already known to be the right length, but bits past the prefix must be
+        // zero.
+        match self.source_prefix % 8 {
+            0 => true,
+            bits => self
+                .address
+                .last()
+                .is_some_and(|byte| byte & (0xff >> bits) == 0),
+        }
+    }
+
+    /// Returns the option to echo back to the client with a given scope prefix
+    pub fn response(&self, scope_prefix: u8) -> Self {
+        Self {
+            scope_prefix,
+            ..self.clone()
+        }
+    }
+}
+
+impl<'read> OptData<'read> for ClientSubnetOpt {
+    fn data_size(&self) -> usize {
+        4 + self.address.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        u16::from(self.family).encode(writer)?;
+        self.source_prefix.encode(writer)?;
+        self.scope_prefix.encode(writer)?;
+        writer.write(&self.address)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        code: OptCode,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(code, OptCode::ClientSubnet);
+
+        if len < 4 {
+            return Err(WireError::InvalidLength {
+                expected: 4,
+                actual: len as usize,
+            });
+        }
+
+        let family = AddressFamily::from(u16::decode(reader)?);
+        let source_prefix = u8::decode(reader)?;
+        let scope_prefix = u8::decode(reader)?;
+
+        // The address must be exactly as long as needed to hold the source prefix.
+        let expected = 4 + (source_prefix as usize).div_ceil(8);
+        if len as usize != expected {
+            return Err(WireError::InvalidLength {
+                expected,
+                actual: len as usize,
+            });
+        }
+
+        let mut address = vec![0; len as usize - 4];
+        reader.read(&mut address)?;
+
+        Ok(Self {
+            family,
+            source_prefix,
+            scope_prefix,
+            address,
+        })
+    }
+
+    fn code(&self) -> OptCode {
+        OptCode::ClientSubnet
+    }
+
+    fn handle(
+        &self,
+        _: &Message,
+        response: &mut Message,
+        context: &mut QueryContext,
+    ) -> OptHandleAction {
+        if !self.validate() {
+            response.set_response_code(ResponseCode::FormatError);
+            return OptHandleAction::ReturnEarly;
+        }
+
+        // The option is echoed once resolving is done, since the scope depends on the answer.
+        context.client_subnet = Some(self.clone());
+
+        OptHandleAction::Nothing
+    }
+}
+
+impl Display for ClientSubnetOpt {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.address() {
+            Some(address) => write!(f, "{}", address)?,
+            None => {
+                write!(f, "FAMILY{} ", u16::from(self.family))?;
+                for byte in &self.address {
+                    write!(f, "{:0>2x}", byte)?;
+                }
+            }
+        }
+
+        write!(f, "/{}/{}", self.source_prefix, self.scope_prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{opt::Opt, wire::to_wire};
+
+    #[test_case(&[0, 1, 24, 0, 192, 0, 2] => true; "ipv4")]
+    #[test_case(&[0, 2, 56, 0, 32, 1, 13, 184, 0, 0, 0] => true; "ipv6")]
+    #[test_case(&[0, 1, 0, 0] => true; "no prefix")]
+    #[test_case(&[0, 1, 24, 8, 192, 0, 2] => false; "scope in query")]
+    #[test_case(&[0, 1, 23, 0, 192, 0, 3] => false; "bits past prefix")]
+    #[test_case(&[0, 1, 40, 0, 192, 0, 2, 1, 0] => false; "prefix too long")]
+    #[test_case(&[0, 3, 8, 0, 192] => false; "unknown family")]
+    fn validate(data: &[u8]) -> bool {
+        ClientSubnetOpt::decode_data(
+            OptCode::ClientSubnet,
+            data.len() as u16,
+            &mut WireRead::new(data),
+        )
+        .unwrap()
+        .validate()
+    }
+
+    #[test_case(&[0, 1]; "short")]
+    #[test_case(&[0, 1, 24, 0, 192, 0]; "short address")]
+    #[test_case(&[0, 1, 24, 0, 192, 0, 2, 0]; "long address")]
+    fn decode_invalid_length(data: &[u8]) {
+        assert!(matches!(
+            ClientSubnetOpt::decode_data(
+                OptCode::ClientSubnet,
+                data.len() as u16,
+                &mut WireRead::new(data),
+            ),
+            Err(WireError::InvalidLength { .. }),
+        ));
+    }
+
+    #[test]
+    fn new_truncates() {
+        let opt = ClientSubnetOpt::new("192.0.2.255".parse().unwrap(), 25, 0);
+
+        assert_eq!(opt.address(), Some("192.0.2.128".parse().unwrap()));
+        assert!(opt.validate());
+    }
+
+    #[test]
+    fn response() {
+        let opt = ClientSubnetOpt::new("2001:db8::1".parse().unwrap(), 56, 0);
+
+        assert_eq!(
+            to_wire(&Opt::from(opt.response(48))).unwrap(),
+            [0, 8, 0, 11, 0, 2, 56, 48, 32, 1, 13, 184, 0, 0, 0],
+        );
+        assert_eq!(opt.response(48).to_string(), "2001:db8::/56/48");
+    }
+}
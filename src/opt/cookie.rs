@@ -10,7 +10,7 @@ use siphasher::sip::SipHasher24;
 use crate::{
     context::{CookieStrategy, QueryContext},
     message::ResponseCode,
-    opt::{OptCode, OptData},
+    opt::{extended_error::ExtendedErrorCode, OptCode, OptData},
     serial::Serial,
     wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
 };
@@ -186,7 +186,9 @@ impl<'read> OptData<'read> for CookieOpt {
         response.add_option(self.response(context).into());
 
         if !self.validate(context) {
-            response.set_response_code(ResponseCode::BadCookie);
+            response
+                .set_response_code(ResponseCode::BadCookie)
+                .add_extended_error(ExtendedErrorCode::Prohibited, "invalid server cookie");
             return OptHandleAction::ReturnEarly;
         }
 
@@ -0,0 +1,103 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    context::QueryContext,
+    message::Message,
+    opt::{OptCode, OptData},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+};
+
+use super::OptHandleAction;
+
+/// An EDNS expire option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpireOpt {
+    expire: Option<u32>,
+}
+
+impl ExpireOpt {
+    /// Constructs a new EDNS expire option
+    pub fn new(expire: Option<u32>) -> Self {
+        Self { expire }
+    }
+
+    /// The seconds until the zone expires, empty in queries
+    pub fn expire(&self) -> Option<u32> {
+        self.expire
+    }
+}
+
+impl<'read> OptData<'read> for ExpireOpt {
+    fn data_size(&self) -> usize {
+        match self.expire {
+            Some(_) => 4,
+            None => 0,
+        }
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        if let Some(expire) = self.expire {
+            expire.encode(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn decode_data(
+        code: OptCode,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(code, OptCode::Expire);
+
+        if len == 0 {
+            return Ok(Self { expire: None });
+        }
+
+        if len != 4 {
+            return Err(WireError::InvalidLength {
+                expected: 4,
+                actual: len as usize,
+            });
+        }
+
+        let expire = Some(u32::decode(reader)?);
+
+        Ok(Self { expire })
+    }
+
+    fn code(&self) -> OptCode {
+        OptCode::Expire
+    }
+
+    fn handle(&self, _: &Message, _: &mut Message, context: &mut QueryContext) -> OptHandleAction {
+        // The expire time is only known once the SOA record has been resolved.
+        context.expire_requested = true;
+
+        OptHandleAction::Nothing
+    }
+}
+
+impl Display for ExpireOpt {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.expire {
+            Some(expire) => write!(f, "{}s", expire),
+            None => write!(f, "<request>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(&[] => Ok(None); "request")]
+    #[test_case(&[0, 0x12, 0x75, 0x00] => Ok(Some(1209600)); "response")]
+    #[test_case(&[0, 0] => Err(WireError::InvalidLength { expected: 4, actual: 2 }); "short")]
+    fn decode(data: &[u8]) -> Result<Option<u32>, WireError> {
+        ExpireOpt::decode_data(OptCode::Expire, data.len() as u16, &mut WireRead::new(data))
+            .map(|opt| opt.expire())
+    }
+}
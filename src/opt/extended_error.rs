@@ -0,0 +1,189 @@
+use std::fmt::{self, Display, Formatter};
+
+use enum_other::other;
+
+use crate::{
+    opt::{OptCode, OptData},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+};
+
+/// An info code from the extended DNS error codes registry
+#[other(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtendedErrorCode {
+    OtherError = 0,
+    UnsupportedDnskeyAlgorithm = 1,
+    UnsupportedDsDigestType = 2,
+    StaleAnswer = 3,
+    ForgedAnswer = 4,
+    DnssecIndeterminate = 5,
+    DnssecBogus = 6,
+    SignatureExpired = 7,
+    SignatureNotYetValid = 8,
+    DnskeyMissing = 9,
+    RrsigsMissing = 10,
+    NoZoneKeyBitSet = 11,
+    NsecMissing = 12,
+    CachedError = 13,
+    NotReady = 14,
+    Blocked = 15,
+    Censored = 16,
+    Filtered = 17,
+    Prohibited = 18,
+    StaleNxdomainAnswer = 19,
+    NotAuthoritative = 20,
+    NotSupported = 21,
+    NoReachableAuthority = 22,
+    NetworkError = 23,
+    InvalidData = 24,
+}
+
+impl Display for ExtendedErrorCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::OtherError => write!(f, "Other Error"),
+            Self::UnsupportedDnskeyAlgorithm => write!(f, "Unsupported DNSKEY Algorithm"),
+            Self::UnsupportedDsDigestType => write!(f, "Unsupported DS Digest Type"),
+            Self::StaleAnswer => write!(f, "Stale Answer"),
+            Self::ForgedAnswer => write!(f, "Forged Answer"),
+            Self::DnssecIndeterminate => write!(f, "DNSSEC Indeterminate"),
+            Self::DnssecBogus => write!(f, "DNSSEC Bogus"),
+            Self::SignatureExpired => write!(f, "Signature Expired"),
+            Self::SignatureNotYetValid => write!(f, "Signature Not Yet Valid"),
+            Self::DnskeyMissing => write!(f, "DNSKEY Missing"),
+            Self::RrsigsMissing => write!(f, "RRSIGs Missing"),
+            Self::NoZoneKeyBitSet => write!(f, "No Zone Key Bit Set"),
+            Self::NsecMissing => write!(f, "NSEC Missing"),
+            Self::CachedError => write!(f, "Cached Error"),
+            Self::NotReady => write!(f, "Not Ready"),
+            Self::Blocked => write!(f, "Blocked"),
+            Self::Censored => write!(f, "Censored"),
+            Self::Filtered => write!(f, "Filtered"),
+            Self::Prohibited => write!(f, "Prohibited"),
+            Self::StaleNxdomainAnswer => write!(f, "Stale NXDOMAIN Answer"),
+            Self::NotAuthoritative => write!(f, "Not Authoritative"),
+            Self::NotSupported => write!(f, "Not Supported"),
+            Self::NoReachableAuthority => write!(f, "No Reachable Authority"),
+            Self::NetworkError => write!(f, "Network Error"),
+            Self::InvalidData => write!(f, "Invalid Data"),
+            Self::Other(code) => write!(f, "{}", code),
+        }
+    }
+}
+
+/// An extended DNS error option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedErrorOpt {
+    info_code: ExtendedErrorCode,
+    extra_text: String,
+}
+
+impl ExtendedErrorOpt {
+    /// Constructs a new extended DNS error option
+    pub fn new(info_code: ExtendedErrorCode, extra_text: &str) -> Self {
+        Self {
+            info_code,
+            extra_text: extra_text.to_string(),
+        }
+    }
+
+    /// The code describing the error
+    pub fn info_code(&self) -> ExtendedErrorCode {
+        self.info_code
+    }
+
+    /// Human readable details about the error, may be empty
+    pub fn extra_text(&self) -> &str {
+        &self.extra_text
+    }
+}
+
+impl<'read> OptData<'read> for ExtendedErrorOpt {
+    fn data_size(&self) -> usize {
+        2 + self.extra_text.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        u16::from(self.info_code).encode(writer)?;
+        writer.write(self.extra_text.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        code: OptCode,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(code, OptCode::ExtendedError);
+
+        if len < 2 {
+            return Err(WireError::InvalidLength {
+                expected: 2,
+                actual: len as usize,
+            });
+        }
+
+        let info_code = ExtendedErrorCode::from(u16::decode(reader)?);
+
+        let mut extra_text = vec![0; len as usize - 2];
+        reader.read(&mut extra_text)?;
+
+        // The text should be UTF-8, but it is only informational so malformed text isn't fatal.
+        let extra_text = String::from_utf8_lossy(&extra_text).into_owned();
+
+        Ok(Self {
+            info_code,
+            extra_text,
+        })
+    }
+
+    fn code(&self) -> OptCode {
+        OptCode::ExtendedError
+    }
+}
+
+impl Display for ExtendedErrorOpt {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} ({})", u16::from(self.info_code), self.info_code)?;
+
+        if !self.extra_text.is_empty() {
+            write!(f, ": {:?}", self.extra_text)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        opt::Opt,
+        wire::{from_wire, to_wire},
+    };
+
+    #[test]
+    fn round_trip() {
+        let opt = Opt::from(ExtendedErrorOpt::new(
+            ExtendedErrorCode::NotAuthoritative,
+            "no zone",
+        ));
+        let wire = to_wire(&opt).unwrap();
+
+        assert_eq!(wire, b"\x00\x0f\x00\x09\x00\x14no zone");
+        assert_eq!(from_wire::<Opt>(&wire), Ok(opt));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ExtendedErrorOpt::new(ExtendedErrorCode::Prohibited, "").to_string(),
+            "18 (Prohibited)",
+        );
+        assert_eq!(
+            ExtendedErrorOpt::new(ExtendedErrorCode::Other(49152), "private").to_string(),
+            "49152 (49152): \"private\"",
+        );
+    }
+}
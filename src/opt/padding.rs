@@ -1,10 +1,14 @@
 use std::fmt::{self, Display, Formatter};
 
 use crate::{
+    context::QueryContext,
+    message::Message,
     opt::{OptCode, OptData},
     wire::{WireError, WireRead, WireWrite},
 };
 
+use super::OptHandleAction;
+
 /// A padding option
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PaddingOpt {
@@ -52,6 +56,21 @@ impl<'read> OptData<'read> for PaddingOpt {
     fn code(&self) -> OptCode {
         OptCode::Padding
     }
+
+    fn handle(
+        &self,
+        _: &Message,
+        response: &mut Message,
+        context: &mut QueryContext,
+    ) -> OptHandleAction {
+        // Clients that pad their queries want padded responses too. The padding is only sized
+        // once the response is final, see `Message::pad_to_block`.
+        if context.config.server.padding_block_size > 0 {
+            response.add_option(PaddingOpt::new(&[]).into());
+        }
+
+        OptHandleAction::Nothing
+    }
 }
 
 impl Display for PaddingOpt {
@@ -10,10 +10,15 @@ use paste::paste;
 use crate::{
     question::Question,
     record::{
-        ch_a::ChARecord, cname::CnameRecord, hinfo::HinfoRecord, in_a::InARecord,
-        in_aaaa::InAaaaRecord, loc::LocRecord, mx::MxRecord, ns::NsRecord, opt::OptRecord,
-        other::OtherRecord, ptr::PtrRecord, rp::RpRecord, soa::SoaRecord, srv::SrvRecord,
-        txt::TxtRecord,
+        afsdb::AfsdbRecord, apl::AplRecord, caa::CaaRecord, cert::CertRecord, ch_a::ChARecord,
+        cname::CnameRecord, eui48::Eui48Record, eui64::Eui64Record, hinfo::HinfoRecord,
+        hip::HipRecord, https::HttpsRecord, in_a::InARecord, in_aaaa::InAaaaRecord,
+        ipseckey::IpseckeyRecord, kx::KxRecord, l32::L32Record, l64::L64Record, loc::LocRecord,
+        lp::LpRecord, mx::MxRecord, naptr::NaptrRecord, nid::NidRecord, ns::NsRecord,
+        openpgpkey::OpenpgpkeyRecord, opt::OptRecord, other::OtherRecord, ptr::PtrRecord,
+        rp::RpRecord, rt::RtRecord, smimea::SmimeaRecord, soa::SoaRecord, srv::SrvRecord,
+        sshfp::SshfpRecord, svcb::SvcbRecord, tlsa::TlsaRecord, txt::TxtRecord, uri::UriRecord,
+        zonemd::ZonemdRecord,
     },
     resolver::ResolveType,
     text::DomainName,
@@ -21,21 +26,47 @@ use crate::{
     zone::{ZoneError, ZoneReader},
 };
 
+pub mod afsdb;
+pub mod apl;
+pub mod caa;
+pub mod cert;
 pub mod ch_a;
 pub mod cname;
+pub mod eui48;
+pub mod eui64;
 pub mod hinfo;
+pub mod hip;
+pub mod https;
 pub mod in_a;
 pub mod in_aaaa;
+pub mod ipseckey;
+pub mod kx;
+pub mod l32;
+pub mod l64;
 pub mod loc;
+pub mod lp;
 pub mod mx;
+pub mod naptr;
+pub mod nid;
 pub mod ns;
+pub mod openpgpkey;
 pub mod opt;
 pub mod other;
 pub mod ptr;
 pub mod rp;
+pub mod rt;
+pub mod smimea;
 pub mod soa;
 pub mod srv;
+pub mod sshfp;
+pub mod svcb;
+pub mod tlsa;
 pub mod txt;
+pub mod uri;
+pub mod zonemd;
+
+mod ilnp;
+mod preference_host;
 
 /// A record or question class
 #[other(u16)]
@@ -75,13 +106,8 @@ impl FromStr for RecordClass {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.starts_with("CLASS") {
-            match s.get("CLASS".len()..) {
-                Some(digits) => {
-                    if let Ok(rclass) = digits.parse::<u16>() {
-                        return Ok(Self::from(rclass));
-                    }
-                }
-                None => (),
+            if let Some(Ok(rclass)) = s.get("CLASS".len()..).map(str::parse::<u16>) {
+                return Ok(Self::from(rclass));
             }
         }
 
@@ -108,10 +134,41 @@ pub enum RecordType {
     Mx = 15,
     Txt = 16,
     Rp = 17,
+    Afsdb = 18,
+    Rt = 21,
     Aaaa = 28,
     Loc = 29,
     Srv = 33,
+    Naptr = 35,
+    Kx = 36,
+    Cert = 37,
     Opt = 41,
+    Apl = 42,
+    Sshfp = 44,
+    Ipseckey = 45,
+    Tlsa = 52,
+    Smimea = 53,
+    Hip = 55,
+    Openpgpkey = 61,
+    Zonemd = 63,
+    Svcb = 64,
+    Https = 65,
+    Nid = 104,
+    L32 = 105,
+    L64 = 106,
+    Lp = 107,
+    Eui48 = 108,
+    Eui64 = 109,
+    Any = 255,
+    Uri = 256,
+    Caa = 257,
+}
+
+impl RecordType {
+    /// Whether the type can only be asked for in a question, like ANY, and never names a record
+    pub fn is_question_only(&self) -> bool {
+        matches!(u16::from(*self), 251..=255)
+    }
 }
 
 impl Display for RecordType {
@@ -126,10 +183,34 @@ impl Display for RecordType {
             Self::Mx => write!(f, "MX"),
             Self::Txt => write!(f, "TXT"),
             Self::Rp => write!(f, "RP"),
+            Self::Afsdb => write!(f, "AFSDB"),
+            Self::Rt => write!(f, "RT"),
             Self::Aaaa => write!(f, "AAAA"),
             Self::Loc => write!(f, "LOC"),
             Self::Srv => write!(f, "SRV"),
+            Self::Naptr => write!(f, "NAPTR"),
+            Self::Kx => write!(f, "KX"),
+            Self::Cert => write!(f, "CERT"),
             Self::Opt => write!(f, "OPT"),
+            Self::Apl => write!(f, "APL"),
+            Self::Sshfp => write!(f, "SSHFP"),
+            Self::Ipseckey => write!(f, "IPSECKEY"),
+            Self::Tlsa => write!(f, "TLSA"),
+            Self::Smimea => write!(f, "SMIMEA"),
+            Self::Hip => write!(f, "HIP"),
+            Self::Openpgpkey => write!(f, "OPENPGPKEY"),
+            Self::Zonemd => write!(f, "ZONEMD"),
+            Self::Svcb => write!(f, "SVCB"),
+            Self::Https => write!(f, "HTTPS"),
+            Self::Nid => write!(f, "NID"),
+            Self::L32 => write!(f, "L32"),
+            Self::L64 => write!(f, "L64"),
+            Self::Lp => write!(f, "LP"),
+            Self::Eui48 => write!(f, "EUI48"),
+            Self::Eui64 => write!(f, "EUI64"),
+            Self::Any => write!(f, "ANY"),
+            Self::Uri => write!(f, "URI"),
+            Self::Caa => write!(f, "CAA"),
             Self::Other(rtype) => write!(f, "TYPE{}", rtype),
         }
     }
@@ -151,13 +232,8 @@ impl FromStr for RecordType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.starts_with("TYPE") {
-            match s.get("TYPE".len()..) {
-                Some(digits) => {
-                    if let Ok(rtype) = digits.parse::<u16>() {
-                        return Ok(Self::from(rtype));
-                    }
-                }
-                None => (),
+            if let Some(Ok(rtype)) = s.get("TYPE".len()..).map(str::parse::<u16>) {
+                return Ok(Self::from(rtype));
             }
         }
 
@@ -171,10 +247,34 @@ impl FromStr for RecordType {
             "MX" => Ok(Self::Mx),
             "TXT" => Ok(Self::Txt),
             "RP" => Ok(Self::Rp),
+            "AFSDB" => Ok(Self::Afsdb),
+            "RT" => Ok(Self::Rt),
             "AAAA" => Ok(Self::Aaaa),
             "LOC" => Ok(Self::Loc),
             "SRV" => Ok(Self::Srv),
+            "NAPTR" => Ok(Self::Naptr),
+            "KX" => Ok(Self::Kx),
+            "CERT" => Ok(Self::Cert),
             "OPT" => Ok(Self::Opt),
+            "APL" => Ok(Self::Apl),
+            "SSHFP" => Ok(Self::Sshfp),
+            "IPSECKEY" => Ok(Self::Ipseckey),
+            "TLSA" => Ok(Self::Tlsa),
+            "SMIMEA" => Ok(Self::Smimea),
+            "HIP" => Ok(Self::Hip),
+            "OPENPGPKEY" => Ok(Self::Openpgpkey),
+            "ZONEMD" => Ok(Self::Zonemd),
+            "SVCB" => Ok(Self::Svcb),
+            "HTTPS" => Ok(Self::Https),
+            "NID" => Ok(Self::Nid),
+            "L32" => Ok(Self::L32),
+            "L64" => Ok(Self::L64),
+            "LP" => Ok(Self::Lp),
+            "EUI48" => Ok(Self::Eui48),
+            "EUI64" => Ok(Self::Eui64),
+            "ANY" => Ok(Self::Any),
+            "URI" => Ok(Self::Uri),
+            "CAA" => Ok(Self::Caa),
             _ => Err(ParseRecordTypeError),
         }
     }
@@ -193,10 +293,33 @@ pub enum Record {
     Mx(MxRecord),
     Txt(TxtRecord),
     Rp(RpRecord),
+    Afsdb(AfsdbRecord),
+    Rt(RtRecord),
     InAaaa(InAaaaRecord),
     Loc(LocRecord),
     Srv(SrvRecord),
+    Naptr(NaptrRecord),
+    Kx(KxRecord),
+    Cert(CertRecord),
     Opt(OptRecord),
+    Apl(AplRecord),
+    Sshfp(SshfpRecord),
+    Ipseckey(IpseckeyRecord),
+    Tlsa(TlsaRecord),
+    Smimea(SmimeaRecord),
+    Hip(HipRecord),
+    Openpgpkey(OpenpgpkeyRecord),
+    Zonemd(ZonemdRecord),
+    Svcb(SvcbRecord),
+    Https(HttpsRecord),
+    Nid(NidRecord),
+    L32(L32Record),
+    L64(L64Record),
+    Lp(LpRecord),
+    Eui48(Eui48Record),
+    Eui64(Eui64Record),
+    Uri(UriRecord),
+    Caa(CaaRecord),
     Other(OtherRecord),
 }
 
@@ -232,6 +355,16 @@ impl<'read> WireDecode<'read> for Record {
     }
 }
 
+/// Displays the data of a record as it appears after the record type in a zone file
+pub struct DisplayData<'record>(&'record Record);
+
+impl Record {
+    /// The data of this record in its zone file representation
+    pub fn display_data(&self) -> DisplayData<'_> {
+        DisplayData(self)
+    }
+}
+
 /// A trait containing common methods to be used in various DNS related operations
 pub trait RecordData<'read>: Sized {
     /// The size of this record's data
@@ -366,8 +499,14 @@ macro_rules! dns_record_impl {
             fn fmt(&self, f: &mut Formatter) -> fmt::Result {
                 write!(f, "{}\t{}\t{}\t{}\t", self.name(), self.ttl(), self.rclass(), self.rtype())?;
 
-                match self {
-                    $(dns_record_impl!(@ variant($rclass, $rtype, data)) => Display::fmt(data, f)),*
+                Display::fmt(&self.display_data(), f)
+            }
+        }
+
+        impl Display for DisplayData<'_> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                match self.0 {
+                    $(dns_record_impl!(@ variant_of(Record, $rclass, $rtype, data)) => Display::fmt(data, f)),*
                 }
             }
         }
@@ -380,17 +519,20 @@ macro_rules! dns_record_impl {
             }
         )*
     };
-    (@ variant(_, _, $ident:ident)) => {
-        Self::Other($ident)
+    (@ variant($rclass:tt, $rtype:tt, $ident:ident)) => {
+        dns_record_impl!(@ variant_of(Self, $rclass, $rtype, $ident))
     };
-    (@ variant(_, $rtype:tt, $ident:ident)) => {
+    (@ variant_of($enum:tt, _, _, $ident:ident)) => {
+        $enum::Other($ident)
+    };
+    (@ variant_of($enum:tt, _, $rtype:tt, $ident:ident)) => {
         paste! {
-            Self::$rtype($ident)
+            $enum::$rtype($ident)
         }
     };
-    (@ variant($rclass:tt, $rtype:tt, $ident:ident)) => {
+    (@ variant_of($enum:tt, $rclass:tt, $rtype:tt, $ident:ident)) => {
         paste! {
-            Self::[<$rclass $rtype>]($ident)
+            $enum::[<$rclass $rtype>]($ident)
         }
     };
     (@ data_struct(_, _)) => {
@@ -428,9 +570,66 @@ dns_record_impl! {
     (_, Mx),
     (_, Txt),
     (_, Rp),
+    (_, Afsdb),
+    (_, Rt),
     (In, Aaaa),
     (_, Loc),
     (_, Srv),
+    (_, Naptr),
+    (_, Kx),
+    (_, Cert),
     (_, Opt),
+    (_, Apl),
+    (_, Sshfp),
+    (_, Ipseckey),
+    (_, Tlsa),
+    (_, Smimea),
+    (_, Hip),
+    (_, Openpgpkey),
+    (_, Zonemd),
+    (_, Svcb),
+    (_, Https),
+    (_, Nid),
+    (_, L32),
+    (_, L64),
+    (_, Lp),
+    (_, Eui48),
+    (_, Eui64),
+    (_, Uri),
+    (_, Caa),
     (_, _),
 }
+
+/// Round trips shared by the tests of each record type
+#[cfg(test)]
+pub(crate) mod round_trip {
+    use super::*;
+    use crate::{
+        node::Node,
+        text::Name,
+        wire::{from_wire, to_wire},
+        zone::read_zone,
+    };
+
+    /// Encodes a record and checks that decoding gives it back, returning the encoding
+    pub(crate) fn wire(record: &Record) -> Vec<u8> {
+        let wire = to_wire(record).unwrap();
+        assert_eq!(from_wire::<Record>(&wire), Ok(record.clone()));
+
+        wire
+    }
+
+    /// Checks that reading the zone file entry of a record gives a tree holding only that record
+    pub(crate) fn zone(record: &Record) {
+        let mut root = Node::new();
+        record
+            .name()
+            .labels()
+            .iter()
+            .rev()
+            .fold(&mut root, |node, label| node.insert(label.clone()))
+            .add_record(record.clone());
+
+        assert_eq!(read_zone(&record.to_string(), Vec::new().into()), Ok(root));
+    }
+}
@@ -0,0 +1,45 @@
+use crate::record::preference_host::preference_host_impl;
+
+preference_host_impl!(
+    /// An AFSDB record
+    AfsdbRecord, Afsdb, "AFSDB",
+    /// The subtype of the server, 1 for an AFS cell database server and 2 for a DCE name server
+    subtype,
+    /// The host of the server
+    hostname,
+);
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::Afsdb(AfsdbRecord::new(
+            "example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            1,
+            "afsdb.example.com.".parse().unwrap(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Afsdb(AfsdbRecord::new(
+            "example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            1,
+            "afsdb.example.com.".parse().unwrap(),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
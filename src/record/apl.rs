@@ -0,0 +1,352 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::DomainName,
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneReader, ZoneToken},
+};
+
+/// An address prefix in an APL record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AplItem {
+    negation: bool,
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl AplItem {
+    /// Constructs a new address prefix
+    pub fn new(negation: bool, address: IpAddr, prefix: u8) -> Self {
+        Self {
+            negation,
+            address,
+            prefix,
+        }
+    }
+
+    /// Whether the prefix is excluded from the list rather than included
+    pub fn negation(&self) -> bool {
+        self.negation
+    }
+
+    /// The address of the prefix
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// The length of the prefix in bits
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// The address family of the prefix, 1 for IPv4 and 2 for IPv6
+    pub fn family(&self) -> u16 {
+        match self.address {
+            IpAddr::V4(_) => 1,
+            IpAddr::V6(_) => 2,
+        }
+    }
+
+    /// The bytes of the address with any trailing zero bytes left out
+    fn address_part(&self) -> Vec<u8> {
+        let mut bytes = match self.address {
+            IpAddr::V4(address) => address.octets().to_vec(),
+            IpAddr::V6(address) => address.octets().to_vec(),
+        };
+
+        while bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+
+        bytes
+    }
+}
+
+impl WireEncode for AplItem {
+    fn size(&self) -> usize {
+        4 + self.address_part().len()
+    }
+
+    fn encode(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        let address_part = self.address_part();
+
+        self.family().encode(writer)?;
+        self.prefix.encode(writer)?;
+        ((u8::from(self.negation) << 7) | address_part.len() as u8).encode(writer)?;
+        writer.write(&address_part)?;
+
+        Ok(())
+    }
+}
+
+impl<'read> WireDecode<'read> for AplItem {
+    fn decode(reader: &mut WireRead<'read>) -> Result<Self, WireError> {
+        let family = u16::decode(reader)?;
+        let prefix = u8::decode(reader)?;
+        let negation_len = u8::decode(reader)?;
+
+        let mut address_part = vec![0; (negation_len & 0x7f) as usize];
+        reader.read(&mut address_part)?;
+
+        let address = match family {
+            1 if address_part.len() <= 4 && prefix <= 32 => {
+                let mut octets = [0; 4];
+                octets[..address_part.len()].copy_from_slice(&address_part);
+                IpAddr::V4(Ipv4Addr::from(octets))
+            }
+            2 if address_part.len() <= 16 && prefix <= 128 => {
+                let mut octets = [0; 16];
+                octets[..address_part.len()].copy_from_slice(&address_part);
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            _ => return Err(WireError::UnsupportedFormat),
+        };
+
+        Ok(Self {
+            negation: negation_len & 0x80 != 0,
+            address,
+            prefix,
+        })
+    }
+}
+
+impl Display for AplItem {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.negation {
+            write!(f, "!")?;
+        }
+
+        write!(f, "{}:{}/{}", self.family(), self.address, self.prefix)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseAplItemError;
+
+impl Display for ParseAplItemError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "provided string was not a valid address prefix")
+    }
+}
+
+impl Error for ParseAplItemError {}
+
+impl FromStr for AplItem {
+    type Err = ParseAplItemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negation, s) = match s.strip_prefix('!') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+
+        let (family, s) = s.split_once(':').ok_or(ParseAplItemError)?;
+        let (address, prefix) = s.rsplit_once('/').ok_or(ParseAplItemError)?;
+        let prefix = prefix.parse::<u8>().map_err(|_| ParseAplItemError)?;
+
+        let address = match family {
+            "1" if prefix <= 32 => IpAddr::V4(address.parse().map_err(|_| ParseAplItemError)?),
+            "2" if prefix <= 128 => IpAddr::V6(address.parse().map_err(|_| ParseAplItemError)?),
+            _ => return Err(ParseAplItemError),
+        };
+
+        Ok(Self {
+            negation,
+            address,
+            prefix,
+        })
+    }
+}
+
+/// An APL record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AplRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    items: Vec<AplItem>,
+}
+
+impl AplRecord {
+    /// Constructs a new APL record
+    pub fn new(name: DomainName, ttl: u32, rclass: RecordClass, items: Vec<AplItem>) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            items,
+        }
+    }
+
+    /// The address prefixes in the list, which may be empty
+    pub fn items(&self) -> &[AplItem] {
+        &self.items
+    }
+}
+
+impl<'read> RecordData<'read> for AplRecord {
+    fn data_size(&self) -> usize {
+        self.items.iter().map(WireEncode::size).sum()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        for item in &self.items {
+            item.encode(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Apl);
+
+        let start = reader.pos();
+
+        let mut items = Vec::new();
+        while reader.pos() < start + len as usize {
+            items.push(AplItem::decode(reader)?);
+        }
+
+        if reader.pos() != start + len as usize {
+            return Err(WireError::InvalidLength {
+                expected: reader.pos() - start,
+                actual: len as usize,
+            });
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            items,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Apl);
+
+        let mut items = Vec::new();
+        loop {
+            match reader.peek() {
+                Some(ZoneToken::String(_)) => items.push(reader.read_parsable()?),
+                Some(_) => _ = reader.read_blank()?,
+                None => break,
+            }
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            items,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Apl
+    }
+}
+
+impl Display for AplRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (i, item) in self.items.iter().enumerate() {
+            if i != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        record::{round_trip, Record},
+        zone::read_zone,
+    };
+
+    fn record(items: &[&str]) -> Record {
+        Record::Apl(AplRecord::new(
+            "nets.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            items.iter().map(|item| item.parse().unwrap()).collect(),
+        ))
+    }
+
+    #[test]
+    fn wire() {
+        let record = record(&["1:192.168.32.0/21", "!1:192.168.38.0/28", "2:ff00::/8"]);
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = record(&["1:192.168.32.0/21", "!1:192.168.38.0/28", "2:ff00::/8"]);
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test]
+    fn zone_empty() {
+        let record = record(&[]);
+
+        round_trip::zone(&record);
+
+        assert_eq!(
+            read_zone("nets.example.com. 3600 IN APL", Vec::new().into()),
+            read_zone(&record.to_string(), Vec::new().into()),
+        );
+    }
+
+    #[test_case("1:192.168.32.0/21" => Some((false, 1, 21)); "ipv4")]
+    #[test_case("!2:2001:db8::/32" => Some((true, 2, 32)); "negated ipv6")]
+    #[test_case("1:192.168.32.0/33" => None; "prefix too long")]
+    #[test_case("2:192.168.32.0/21" => None; "wrong family")]
+    #[test_case("3:192.168.32.0/21" => None; "unknown family")]
+    #[test_case("1:192.168.32.0" => None; "missing prefix")]
+    fn item(item: &str) -> Option<(bool, u16, u8)> {
+        item.parse::<AplItem>()
+            .ok()
+            .map(|item| (item.negation(), item.family(), item.prefix()))
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::{write_quoted, DomainName},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader},
+};
+
+/// The property tag of a CAA record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaaTag {
+    Issue,
+    IssueWild,
+    Iodef,
+    Other(String),
+}
+
+impl Display for CaaTag {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Issue => write!(f, "issue"),
+            Self::IssueWild => write!(f, "issuewild"),
+            Self::Iodef => write!(f, "iodef"),
+            Self::Other(tag) => write!(f, "{}", tag),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseCaaTagError;
+
+impl Display for ParseCaaTagError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "provided string was not a valid CAA property tag")
+    }
+}
+
+impl Error for ParseCaaTagError {}
+
+impl FromStr for CaaTag {
+    type Err = ParseCaaTagError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.len() > 15 || !s.bytes().all(|byte| byte.is_ascii_alphanumeric()) {
+            return Err(ParseCaaTagError);
+        }
+
+        // Tags are matched case insensitively.
+        match s.to_ascii_lowercase().as_str() {
+            "issue" => Ok(Self::Issue),
+            "issuewild" => Ok(Self::IssueWild),
+            "iodef" => Ok(Self::Iodef),
+            _ => Ok(Self::Other(s.to_string())),
+        }
+    }
+}
+
+/// A CAA record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaaRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    flags: u8,
+    tag: CaaTag,
+    value: Vec<u8>,
+}
+
+impl CaaRecord {
+    /// The flag marking a property that must be understood by the issuer
+    pub const ISSUER_CRITICAL: u8 = 0x80;
+
+    /// Constructs a new CAA record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        flags: u8,
+        tag: CaaTag,
+        value: Vec<u8>,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            flags,
+            tag,
+            value,
+        }
+    }
+
+    /// The flags of the property
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Whether the issuer must understand the property to issue a certificate
+    pub fn is_critical(&self) -> bool {
+        self.flags & Self::ISSUER_CRITICAL != 0
+    }
+
+    /// The property tag
+    pub fn tag(&self) -> &CaaTag {
+        &self.tag
+    }
+
+    /// The property value, its meaning depends on the tag
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn tag_len(&self) -> usize {
+        self.tag.to_string().len()
+    }
+}
+
+impl<'read> RecordData<'read> for CaaRecord {
+    fn data_size(&self) -> usize {
+        2 + self.tag_len() + self.value.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        let tag = self.tag.to_string();
+
+        self.flags.encode(writer)?;
+        (tag.len() as u8).encode(writer)?;
+        writer.write(tag.as_bytes())?;
+        writer.write(&self.value)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Caa);
+
+        let flags = u8::decode(reader)?;
+        let tag_len = u8::decode(reader)? as usize;
+
+        if 2 + tag_len > len as usize {
+            return Err(WireError::InvalidLength {
+                expected: 2 + tag_len,
+                actual: len as usize,
+            });
+        }
+
+        let mut tag = vec![0; tag_len];
+        reader.read(&mut tag)?;
+        let tag = String::from_utf8(tag)
+            .ok()
+            .and_then(|tag| tag.parse().ok())
+            .ok_or(WireError::UnsupportedFormat)?;
+
+        let mut value = vec![0; len as usize - 2 - tag_len];
+        reader.read(&mut value)?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            flags,
+            tag,
+            value,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Caa);
+
+        let flags = reader.read_parsable()?;
+        reader.read_blank()?;
+        let tag = reader.read_parsable()?;
+        reader.read_blank()?;
+        let value = reader.read_text()?.into();
+
+        if flags & !Self::ISSUER_CRITICAL != 0 {
+            return reader.error(ZoneErrorKind::BadEntry);
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            flags,
+            tag,
+            value,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Caa
+    }
+}
+
+impl Display for CaaRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {} ", self.flags, self.tag)?;
+        write_quoted(f, &self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::record::{round_trip, Record};
+
+    #[test]
+    fn wire() {
+        let record = Record::Caa(CaaRecord::new(
+            "example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            0,
+            CaaTag::Issue,
+            b"ca.example.net".to_vec(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Caa(CaaRecord::new(
+            "example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            CaaRecord::ISSUER_CRITICAL,
+            CaaTag::Iodef,
+            b"mailto:\"security\"@example.com".to_vec(),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case("issue" => Some(CaaTag::Issue); "issue")]
+    #[test_case("IssueWild" => Some(CaaTag::IssueWild); "case insensitive")]
+    #[test_case("tbs" => Some(CaaTag::Other("tbs".to_string())); "other")]
+    #[test_case("" => None; "empty")]
+    #[test_case("content-type" => None; "not alphanumeric")]
+    #[test_case("abcdefghijklmnop" => None; "too long")]
+    fn tag(tag: &str) -> Option<CaaTag> {
+        tag.parse().ok()
+    }
+}
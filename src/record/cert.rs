@@ -0,0 +1,294 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use enum_other::other;
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::DomainName,
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneReader},
+};
+
+/// The format of the certificate in a CERT record
+#[other(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertType {
+    Pkix = 1,
+    Spki = 2,
+    Pgp = 3,
+    Ipkix = 4,
+    Ispki = 5,
+    Ipgp = 6,
+    Acpkix = 7,
+    Iacpkix = 8,
+    Uri = 253,
+    Oid = 254,
+}
+
+impl Display for CertType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Pkix => write!(f, "PKIX"),
+            Self::Spki => write!(f, "SPKI"),
+            Self::Pgp => write!(f, "PGP"),
+            Self::Ipkix => write!(f, "IPKIX"),
+            Self::Ispki => write!(f, "ISPKI"),
+            Self::Ipgp => write!(f, "IPGP"),
+            Self::Acpkix => write!(f, "ACPKIX"),
+            Self::Iacpkix => write!(f, "IACPKIX"),
+            Self::Uri => write!(f, "URI"),
+            Self::Oid => write!(f, "OID"),
+            Self::Other(ctype) => write!(f, "{}", ctype),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseCertTypeError;
+
+impl Display for ParseCertTypeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "provided string was not a recognised certificate type")
+    }
+}
+
+impl Error for ParseCertTypeError {}
+
+impl FromStr for CertType {
+    type Err = ParseCertTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(ctype) = s.parse::<u16>() {
+            return Ok(Self::from(ctype));
+        }
+
+        match s {
+            "PKIX" => Ok(Self::Pkix),
+            "SPKI" => Ok(Self::Spki),
+            "PGP" => Ok(Self::Pgp),
+            "IPKIX" => Ok(Self::Ipkix),
+            "ISPKI" => Ok(Self::Ispki),
+            "IPGP" => Ok(Self::Ipgp),
+            "ACPKIX" => Ok(Self::Acpkix),
+            "IACPKIX" => Ok(Self::Iacpkix),
+            "URI" => Ok(Self::Uri),
+            "OID" => Ok(Self::Oid),
+            _ => Err(ParseCertTypeError),
+        }
+    }
+}
+
+/// A CERT record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    ctype: CertType,
+    key_tag: u16,
+    algorithm: u8,
+    certificate: Vec<u8>,
+}
+
+impl CertRecord {
+    /// Constructs a new CERT record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        ctype: CertType,
+        key_tag: u16,
+        algorithm: u8,
+        certificate: Vec<u8>,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            ctype,
+            key_tag,
+            algorithm,
+            certificate,
+        }
+    }
+
+    /// The format of the certificate
+    pub fn ctype(&self) -> CertType {
+        self.ctype
+    }
+
+    /// The tag of the key the certificate is for, zero if unused
+    pub fn key_tag(&self) -> u16 {
+        self.key_tag
+    }
+
+    /// The DNSSEC algorithm of the key the certificate is for, zero if unused
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    /// The certificate, or the location of it for indirect types
+    pub fn certificate(&self) -> &[u8] {
+        &self.certificate
+    }
+}
+
+impl<'read> RecordData<'read> for CertRecord {
+    fn data_size(&self) -> usize {
+        5 + self.certificate.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        u16::from(self.ctype).encode(writer)?;
+        self.key_tag.encode(writer)?;
+        self.algorithm.encode(writer)?;
+        writer.write(&self.certificate)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Cert);
+
+        if len < 5 {
+            return Err(WireError::InvalidLength {
+                expected: 5,
+                actual: len as usize,
+            });
+        }
+
+        let ctype = CertType::from(u16::decode(reader)?);
+        let key_tag = u16::decode(reader)?;
+        let algorithm = u8::decode(reader)?;
+
+        let mut certificate = vec![0; len as usize - 5];
+        reader.read(&mut certificate)?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            ctype,
+            key_tag,
+            algorithm,
+            certificate,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Cert);
+
+        let ctype = reader.read_parsable()?;
+        reader.read_blank()?;
+        let key_tag = reader.read_parsable()?;
+        reader.read_blank()?;
+        let algorithm = reader.read_parsable()?;
+        reader.read_blank()?;
+        let certificate = reader.read_base64()?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            ctype,
+            key_tag,
+            algorithm,
+            certificate,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Cert
+    }
+}
+
+impl Display for CertRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.ctype,
+            self.key_tag,
+            self.algorithm,
+            BASE64.encode(&self.certificate),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::record::{round_trip, Record};
+
+    #[test]
+    fn wire() {
+        let record = Record::Cert(CertRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            CertType::Pkix,
+            0,
+            0,
+            b"\x30\x82".to_vec(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Cert(CertRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            CertType::Ipgp,
+            12345,
+            8,
+            b"\x30\x82".to_vec(),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case("PGP" => Some(CertType::Pgp); "mnemonic")]
+    #[test_case("3" => Some(CertType::Pgp); "number")]
+    #[test_case("65280" => Some(CertType::Other(65280)); "private")]
+    #[test_case("X509" => None; "unknown")]
+    fn ctype(ctype: &str) -> Option<CertType> {
+        ctype.parse().ok()
+    }
+}
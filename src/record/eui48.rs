@@ -0,0 +1,192 @@
+use std::fmt::{self, Formatter};
+
+/// Parses an EUI written as hyphenated pairs of hex digits, such as `00-00-5e-00-53-2a`, checking
+/// that it's `len` bytes long.
+pub(crate) fn parse_eui(s: &str, len: usize) -> Option<Vec<u8>> {
+    let parts = s.split('-').collect::<Vec<_>>();
+    if parts.len() != len || parts.iter().any(|part| part.len() != 2) {
+        return None;
+    }
+
+    hex::decode(parts.concat()).ok()
+}
+
+/// Writes an EUI as hyphenated pairs of lowercase hex digits.
+pub(crate) fn write_eui(f: &mut Formatter, address: &[u8]) -> fmt::Result {
+    for (i, byte) in address.iter().enumerate() {
+        if i != 0 {
+            write!(f, "-")?;
+        }
+        write!(f, "{:02x}", byte)?;
+    }
+
+    Ok(())
+}
+
+/// Implements a record type holding an EUI address of a fixed length, such as a MAC address.
+macro_rules! eui_impl {
+    ($record:ident, $rtype:ident, $doc:literal, $len:literal) => {
+        #[doc = concat!("An ", $doc, " record")]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $record {
+            name: $crate::text::DomainName,
+            ttl: u32,
+            rclass: $crate::record::RecordClass,
+            address: [u8; $len],
+        }
+
+        impl $record {
+            #[doc = concat!("Constructs a new ", $doc, " record")]
+            pub fn new(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                address: [u8; $len],
+            ) -> Self {
+                Self {
+                    name,
+                    ttl,
+                    rclass,
+                    address,
+                }
+            }
+
+            /// The EUI address of the owner
+            pub fn address(&self) -> [u8; $len] {
+                self.address
+            }
+        }
+
+        impl<'read> $crate::record::RecordData<'read> for $record {
+            fn data_size(&self) -> usize {
+                $len
+            }
+
+            fn encode_data(
+                &self,
+                writer: &mut $crate::wire::WireWrite,
+            ) -> Result<(), $crate::wire::WireError> {
+                writer.write(&self.address)
+            }
+
+            fn decode_data(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                len: u16,
+                reader: &mut $crate::wire::WireRead<'read>,
+            ) -> Result<Self, $crate::wire::WireError> {
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                if len != $len {
+                    return Err($crate::wire::WireError::InvalidLength {
+                        expected: $len,
+                        actual: len as usize,
+                    });
+                }
+
+                let mut address = [0; $len];
+                reader.read(&mut address)?;
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    address,
+                })
+            }
+
+            fn decode_zone(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                reader: &mut $crate::zone::ZoneReader,
+            ) -> Result<Self, $crate::zone::ZoneError> {
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                let address = reader.read_string()?;
+                let Some(address) = $crate::record::eui48::parse_eui(&address, $len) else {
+                    return reader.error($crate::zone::ZoneErrorKind::BadEntry);
+                };
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    address: address.try_into().unwrap(),
+                })
+            }
+
+            fn name(&self) -> &$crate::text::DomainName {
+                &self.name
+            }
+
+            fn ttl(&self) -> u32 {
+                self.ttl
+            }
+
+            fn rclass(&self) -> $crate::record::RecordClass {
+                self.rclass
+            }
+
+            fn rtype(&self) -> $crate::record::RecordType {
+                $crate::record::RecordType::$rtype
+            }
+        }
+
+        impl std::fmt::Display for $record {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $crate::record::eui48::write_eui(f, &self.address)
+            }
+        }
+    };
+}
+
+pub(crate) use eui_impl;
+
+eui_impl!(Eui48Record, Eui48, "EUI48", 6);
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::record::{round_trip, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::Eui48(Eui48Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            [0x00, 0x00, 0x5e, 0x00, 0x53, 0x2a],
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Eui48(Eui48Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            [0x00, 0x00, 0x5e, 0x00, 0x53, 0x2a],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case("00-00-5E-00-53-2A" => Some(vec![0x00, 0x00, 0x5e, 0x00, 0x53, 0x2a]); "uppercase")]
+    #[test_case("00-00-5e-00-53" => None; "too short")]
+    #[test_case("00:00:5e:00:53:2a" => None; "colons")]
+    #[test_case("000-0-5e-00-53-2a" => None; "uneven groups")]
+    #[test_case("00-00-5e-00-53-zz" => None; "not hex")]
+    fn parse(address: &str) -> Option<Vec<u8>> {
+        parse_eui(address, 6)
+    }
+}
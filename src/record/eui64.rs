@@ -0,0 +1,36 @@
+use crate::record::eui48::eui_impl;
+
+eui_impl!(Eui64Record, Eui64, "EUI64", 8);
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::Eui64(Eui64Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            [0x00, 0x00, 0x5e, 0xef, 0x10, 0x00, 0x00, 0x2a],
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Eui64(Eui64Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            [0x00, 0x00, 0x5e, 0xef, 0x10, 0x00, 0x00, 0x2a],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
@@ -0,0 +1,303 @@
+use std::fmt::{self, Display, Formatter};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::{DomainName, HostName},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader, ZoneToken},
+};
+
+/// A HIP record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HipRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    algorithm: u8,
+    hit: Vec<u8>,
+    key: Vec<u8>,
+    rendezvous_servers: Vec<HostName>,
+}
+
+impl HipRecord {
+    /// Constructs a new HIP record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        algorithm: u8,
+        hit: Vec<u8>,
+        key: Vec<u8>,
+        rendezvous_servers: Vec<HostName>,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            algorithm,
+            hit,
+            key,
+            rendezvous_servers,
+        }
+    }
+
+    /// The algorithm of the host identity public key
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    /// The host identity tag, a hash of the public key
+    pub fn hit(&self) -> &[u8] {
+        &self.hit
+    }
+
+    /// The host identity public key
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The rendezvous servers the host can be reached through, in order of preference
+    pub fn rendezvous_servers(&self) -> &[HostName] {
+        &self.rendezvous_servers
+    }
+}
+
+impl<'read> RecordData<'read> for HipRecord {
+    fn data_size(&self) -> usize {
+        4 + self.hit.len()
+            + self.key.len()
+            + self
+                .rendezvous_servers
+                .iter()
+                .map(WireEncode::size)
+                .sum::<usize>()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        (self.hit.len() as u8).encode(writer)?;
+        self.algorithm.encode(writer)?;
+        (self.key.len() as u16).encode(writer)?;
+        writer.write(&self.hit)?;
+        writer.write(&self.key)?;
+        for server in &self.rendezvous_servers {
+            server.encode(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Hip);
+
+        let start = reader.pos();
+
+        let hit_len = u8::decode(reader)?;
+        let algorithm = u8::decode(reader)?;
+        let key_len = u16::decode(reader)?;
+
+        let mut hit = vec![0; hit_len as usize];
+        reader.read(&mut hit)?;
+        let mut key = vec![0; key_len as usize];
+        reader.read(&mut key)?;
+
+        let mut rendezvous_servers = Vec::new();
+        while reader.pos() < start + len as usize {
+            rendezvous_servers.push(HostName::decode(reader)?);
+        }
+
+        if reader.pos() != start + len as usize {
+            return Err(WireError::InvalidLength {
+                expected: reader.pos() - start,
+                actual: len as usize,
+            });
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            algorithm,
+            hit,
+            key,
+            rendezvous_servers,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Hip);
+
+        let algorithm = reader.read_parsable()?;
+        reader.read_blank()?;
+
+        let Ok(hit) = hex::decode(reader.read_string()?) else {
+            return reader.error(ZoneErrorKind::BadEntry);
+        };
+        reader.read_blank()?;
+
+        // Unlike other base64 data, the key is a single string so that every string after it can
+        // be read as a rendezvous server.
+        let Ok(key) = BASE64.decode(reader.read_string()?) else {
+            return reader.error(ZoneErrorKind::BadEntry);
+        };
+
+        if hit.is_empty() || hit.len() > u8::MAX as usize || key.is_empty() {
+            return reader.error(ZoneErrorKind::BadEntry);
+        }
+
+        let mut rendezvous_servers = Vec::new();
+        loop {
+            match reader.peek() {
+                Some(ZoneToken::String(_)) => rendezvous_servers.push(reader.read_name()?.into()),
+                Some(_) => _ = reader.read_blank()?,
+                None => break,
+            }
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            algorithm,
+            hit,
+            key,
+            rendezvous_servers,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Hip
+    }
+}
+
+impl Display for HipRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.algorithm,
+            hex::encode(&self.hit),
+            BASE64.encode(&self.key),
+        )?;
+
+        for server in &self.rendezvous_servers {
+            write!(f, " {}", server)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::{
+        record::{round_trip, Record},
+        zone::read_zone,
+    };
+
+    const HIT: &str = "200100107b1a74df365639cc39f1d578";
+
+    fn record(rendezvous_servers: &[&str]) -> Record {
+        Record::Hip(HipRecord::new(
+            "www.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            2,
+            hex::decode(HIT).unwrap(),
+            b"\x01\x03\x51\x53".to_vec(),
+            rendezvous_servers
+                .iter()
+                .map(|server| server.parse().unwrap())
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn wire() {
+        let record = record(&["rvs1.example.com.", "rvs2.example.com."]);
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = record(&["rvs.example.com."]);
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test]
+    fn zone_without_servers() {
+        let record = record(&[]);
+
+        round_trip::zone(&record);
+    }
+
+    #[test]
+    fn zone_multiline() {
+        let record = record(&["rvs1.example.com.", "rvs2.example.com."]);
+
+        let source = format!(
+            "www.example.com. 3600 IN HIP 2 {} AQNRUw== (\n\trvs1.example.com.\n\
+             \trvs2.example.com. )",
+            HIT
+        );
+        assert_eq!(
+            read_zone(&source, Vec::new().into()),
+            read_zone(&record.to_string(), Vec::new().into()),
+        );
+    }
+
+    #[test]
+    fn zone_relative_servers() {
+        // A key of six bytes has no padding to mark its end.
+        let record = Record::Hip(HipRecord::new(
+            "www.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            2,
+            hex::decode(HIT).unwrap(),
+            hex::decode("010351537a9e").unwrap(),
+            vec![
+                "rvs1.example.com.".parse().unwrap(),
+                "rvs2.example.net.".parse().unwrap(),
+            ],
+        ));
+
+        let source = format!("www 3600 IN HIP 2 {} AQNRU3qe rvs1 rvs2.example.net.", HIT);
+        assert_eq!(
+            read_zone(&source, "example.com.".parse().unwrap()),
+            read_zone(&record.to_string(), Vec::new().into()),
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::record::svcb::svcb_impl;
+
+svcb_impl!(HttpsRecord, Https, "HTTPS");
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, svcb::SvcParam, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::Https(HttpsRecord::new(
+            "example.com.".parse().unwrap(),
+            300,
+            RecordClass::In,
+            0,
+            "pool.svc.example.com.".parse().unwrap(),
+            Vec::new(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Https(HttpsRecord::new(
+            "example.com.".parse().unwrap(),
+            300,
+            RecordClass::In,
+            1,
+            ".".parse().unwrap(),
+            vec![
+                SvcParam::Alpn(vec![b"h3".to_vec(), b"h2".to_vec()]),
+                SvcParam::Port(8443),
+            ],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
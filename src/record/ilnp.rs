@@ -0,0 +1,195 @@
+use std::fmt::{self, Formatter};
+
+/// Parses a 64-bit value written as four colon separated groups of hex digits, such as
+/// `2001:0db8:1140:1000`, the form shared by locators and node identifiers.
+pub(crate) fn parse_colon_hex(s: &str) -> Option<u64> {
+    let groups = s.split(':').collect::<Vec<_>>();
+    if groups.len() != 4 {
+        return None;
+    }
+
+    groups.into_iter().try_fold(0u64, |value, group| {
+        if group.is_empty() || group.len() > 4 || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        Some((value << 16) | u64::from_str_radix(group, 16).ok()?)
+    })
+}
+
+/// Writes a 64-bit value as four colon separated groups of four lowercase hex digits.
+pub(crate) fn write_colon_hex(f: &mut Formatter, value: u64) -> fmt::Result {
+    write!(
+        f,
+        "{:04x}:{:04x}:{:04x}:{:04x}",
+        value >> 48,
+        (value >> 32) & 0xffff,
+        (value >> 16) & 0xffff,
+        value & 0xffff,
+    )
+}
+
+/// Implements a record type holding a 16-bit preference and a 64-bit ILNPv6 value written as
+/// colon separated hex groups, the data format shared by NID and L64.
+macro_rules! ilnp64_impl {
+    (
+        $(#[$doc:meta])* $record:ident, $rtype:ident, $name:literal,
+        $(#[$preference_doc:meta])* preference,
+        $(#[$value_doc:meta])* $value:ident $(,)?
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $record {
+            name: $crate::text::DomainName,
+            ttl: u32,
+            rclass: $crate::record::RecordClass,
+            preference: u16,
+            $value: u64,
+        }
+
+        impl $record {
+            #[doc = concat!("Constructs a new ", $name, " record")]
+            pub fn new(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                preference: u16,
+                $value: u64,
+            ) -> Self {
+                Self {
+                    name,
+                    ttl,
+                    rclass,
+                    preference,
+                    $value,
+                }
+            }
+
+            $(#[$preference_doc])*
+            pub fn preference(&self) -> u16 {
+                self.preference
+            }
+
+            $(#[$value_doc])*
+            pub fn $value(&self) -> u64 {
+                self.$value
+            }
+        }
+
+        impl<'read> $crate::record::RecordData<'read> for $record {
+            fn data_size(&self) -> usize {
+                10
+            }
+
+            fn encode_data(
+                &self,
+                writer: &mut $crate::wire::WireWrite,
+            ) -> Result<(), $crate::wire::WireError> {
+                use $crate::wire::WireEncode;
+
+                self.preference.encode(writer)?;
+                self.$value.encode(writer)?;
+
+                Ok(())
+            }
+
+            fn decode_data(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                len: u16,
+                reader: &mut $crate::wire::WireRead<'read>,
+            ) -> Result<Self, $crate::wire::WireError> {
+                use $crate::wire::WireDecode;
+
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                if len != 10 {
+                    return Err($crate::wire::WireError::InvalidLength {
+                        expected: 10,
+                        actual: len as usize,
+                    });
+                }
+
+                let preference = u16::decode(reader)?;
+                let $value = u64::decode(reader)?;
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    preference,
+                    $value,
+                })
+            }
+
+            fn decode_zone(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                reader: &mut $crate::zone::ZoneReader,
+            ) -> Result<Self, $crate::zone::ZoneError> {
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                let preference = reader.read_parsable()?;
+                reader.read_blank()?;
+                let Some($value) = $crate::record::ilnp::parse_colon_hex(&reader.read_string()?)
+                else {
+                    return reader.error($crate::zone::ZoneErrorKind::BadEntry);
+                };
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    preference,
+                    $value,
+                })
+            }
+
+            fn name(&self) -> &$crate::text::DomainName {
+                &self.name
+            }
+
+            fn ttl(&self) -> u32 {
+                self.ttl
+            }
+
+            fn rclass(&self) -> $crate::record::RecordClass {
+                self.rclass
+            }
+
+            fn rtype(&self) -> $crate::record::RecordType {
+                $crate::record::RecordType::$rtype
+            }
+        }
+
+        impl std::fmt::Display for $record {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{} ", self.preference)?;
+                $crate::record::ilnp::write_colon_hex(f, self.$value)
+            }
+        }
+    };
+}
+
+pub(crate) use ilnp64_impl;
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("2001:0DB8:1140:1000" => Some(0x2001_0db8_1140_1000); "uppercase")]
+    #[test_case("2001:db8:1140:1000" => Some(0x2001_0db8_1140_1000); "short group")]
+    #[test_case("2001:0db8:1140" => None; "too few groups")]
+    #[test_case("2001:0db8::1000" => None; "empty group")]
+    #[test_case("2001:0db8:1140:10000" => None; "long group")]
+    #[test_case("2001:0db8:1140:+100" => None; "sign")]
+    fn parse(locator: &str) -> Option<u64> {
+        parse_colon_hex(locator)
+    }
+}
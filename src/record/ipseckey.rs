@@ -0,0 +1,301 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::{DomainName, HostName},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader},
+};
+
+/// The gateway of an IPSECKEY record, its variant is given by the gateway type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Gateway {
+    None,
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+    Name(HostName),
+}
+
+impl Gateway {
+    /// The gateway type of the gateway
+    pub fn gateway_type(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Ipv4(_) => 1,
+            Self::Ipv6(_) => 2,
+            Self::Name(_) => 3,
+        }
+    }
+}
+
+impl WireEncode for Gateway {
+    fn size(&self) -> usize {
+        match self {
+            Self::None => 0,
+            Self::Ipv4(_) => 4,
+            Self::Ipv6(_) => 16,
+            Self::Name(name) => name.size(),
+        }
+    }
+
+    fn encode(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        match self {
+            Self::None => Ok(()),
+            Self::Ipv4(addr) => u32::from(*addr).encode(writer),
+            Self::Ipv6(addr) => u128::from(*addr).encode(writer),
+            Self::Name(name) => name.encode(writer),
+        }
+    }
+}
+
+impl Display for Gateway {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "."),
+            Self::Ipv4(addr) => write!(f, "{}", addr),
+            Self::Ipv6(addr) => write!(f, "{}", addr),
+            Self::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// An IPSECKEY record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpseckeyRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    precedence: u8,
+    algorithm: u8,
+    gateway: Gateway,
+    key: Vec<u8>,
+}
+
+impl IpseckeyRecord {
+    /// Constructs a new IPSECKEY record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        precedence: u8,
+        algorithm: u8,
+        gateway: Gateway,
+        key: Vec<u8>,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            precedence,
+            algorithm,
+            gateway,
+            key,
+        }
+    }
+
+    /// The precedence of the gateway, lowest first
+    pub fn precedence(&self) -> u8 {
+        self.precedence
+    }
+
+    /// The algorithm of the public key, zero if there is no key
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    /// The gateway to send IPsec traffic to
+    pub fn gateway(&self) -> &Gateway {
+        &self.gateway
+    }
+
+    /// The public key of the gateway, may be empty
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl<'read> RecordData<'read> for IpseckeyRecord {
+    fn data_size(&self) -> usize {
+        3 + self.gateway.size() + self.key.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        self.precedence.encode(writer)?;
+        self.gateway.gateway_type().encode(writer)?;
+        self.algorithm.encode(writer)?;
+        self.gateway.encode(writer)?;
+        writer.write(&self.key)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Ipseckey);
+
+        let precedence = u8::decode(reader)?;
+        let gateway_type = u8::decode(reader)?;
+        let algorithm = u8::decode(reader)?;
+
+        let gateway = match gateway_type {
+            0 => Gateway::None,
+            1 => Gateway::Ipv4(u32::decode(reader)?.into()),
+            2 => Gateway::Ipv6(u128::decode(reader)?.into()),
+            3 => Gateway::Name(HostName::decode(reader)?),
+            _ => return Err(WireError::UnsupportedFormat),
+        };
+
+        if 3 + gateway.size() > len as usize {
+            return Err(WireError::InvalidLength {
+                expected: 3 + gateway.size(),
+                actual: len as usize,
+            });
+        }
+
+        let mut key = vec![0; len as usize - 3 - gateway.size()];
+        reader.read(&mut key)?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            precedence,
+            algorithm,
+            gateway,
+            key,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Ipseckey);
+
+        let precedence = reader.read_parsable()?;
+        reader.read_blank()?;
+        let gateway_type: u8 = reader.read_parsable()?;
+        reader.read_blank()?;
+        let algorithm = reader.read_parsable()?;
+        reader.read_blank()?;
+
+        let gateway = match gateway_type {
+            0 => match reader.read_string()?.as_str() {
+                "." => Gateway::None,
+                _ => return reader.error(ZoneErrorKind::BadEntry),
+            },
+            1 => Gateway::Ipv4(reader.read_parsable()?),
+            2 => Gateway::Ipv6(reader.read_parsable()?),
+            3 => Gateway::Name(reader.read_name()?.into()),
+            _ => return reader.error(ZoneErrorKind::BadEntry),
+        };
+
+        let key = reader.read_base64()?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            precedence,
+            algorithm,
+            gateway,
+            key,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Ipseckey
+    }
+}
+
+impl Display for IpseckeyRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.precedence,
+            self.gateway.gateway_type(),
+            self.algorithm,
+            self.gateway,
+        )?;
+
+        if !self.key.is_empty() {
+            write!(f, " {}", BASE64.encode(&self.key))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::record::{round_trip, Record};
+
+    fn record(gateway: Gateway, key: &[u8]) -> Record {
+        Record::Ipseckey(IpseckeyRecord::new(
+            "38.2.0.192.in-addr.arpa.".parse().unwrap(),
+            7200,
+            RecordClass::In,
+            10,
+            match key.is_empty() {
+                true => 0,
+                false => 2,
+            },
+            gateway,
+            key.to_vec(),
+        ))
+    }
+
+    #[test_case(Gateway::None; "none")]
+    #[test_case(Gateway::Ipv4(Ipv4Addr::new(192, 0, 2, 38)); "ipv4")]
+    #[test_case(Gateway::Ipv6("2001:db8:0:8002::2000:1".parse().unwrap()); "ipv6")]
+    #[test_case(Gateway::Name("mygateway.example.com.".parse().unwrap()); "name")]
+    fn wire(gateway: Gateway) {
+        let name = format!("wire_gateway_{}", gateway.gateway_type());
+        let record = record(gateway, b"\x01\x03\x51\x53");
+
+        assert_debug_snapshot!(name, round_trip::wire(&record));
+    }
+
+    #[test_case(Gateway::None, b""; "none")]
+    #[test_case(Gateway::Ipv4(Ipv4Addr::new(192, 0, 2, 38)), b"\x01\x03\x51\x53"; "ipv4")]
+    #[test_case(Gateway::Ipv6("2001:db8:0:8002::2000:1".parse().unwrap()), b"\x01\x03\x51\x53"; "ipv6")]
+    #[test_case(Gateway::Name("mygateway.example.com.".parse().unwrap()), b"\x01\x03\x51\x53"; "name")]
+    fn zone(gateway: Gateway, key: &[u8]) {
+        let name = format!("zone_gateway_{}", gateway.gateway_type());
+        let record = record(gateway, key);
+
+        assert_display_snapshot!(name, record);
+        round_trip::zone(&record);
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    net::Ipv4Addr,
+};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::DomainName,
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneReader},
+};
+
+/// An L32 record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L32Record {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    preference: u16,
+    locator: Ipv4Addr,
+}
+
+impl L32Record {
+    /// Constructs a new L32 record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        preference: u16,
+        locator: Ipv4Addr,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            preference,
+            locator,
+        }
+    }
+
+    /// The preference of the locator, lowest first
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// The 32-bit ILNPv4 locator, the address of an IPv4 subnet
+    pub fn locator(&self) -> Ipv4Addr {
+        self.locator
+    }
+}
+
+impl<'read> RecordData<'read> for L32Record {
+    fn data_size(&self) -> usize {
+        6
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        self.preference.encode(writer)?;
+        u32::from(self.locator).encode(writer)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::L32);
+
+        if len != 6 {
+            return Err(WireError::InvalidLength {
+                expected: 6,
+                actual: len as usize,
+            });
+        }
+
+        let preference = u16::decode(reader)?;
+        let locator = u32::decode(reader)?.into();
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            preference,
+            locator,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::L32);
+
+        let preference = reader.read_parsable()?;
+        reader.read_blank()?;
+        let locator = reader.read_parsable()?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            preference,
+            locator,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::L32
+    }
+}
+
+impl Display for L32Record {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.preference, self.locator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record};
+
+    #[test]
+    fn wire() {
+        let record = Record::L32(L32Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            Ipv4Addr::new(10, 1, 2, 0),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::L32(L32Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            Ipv4Addr::new(10, 1, 2, 0),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::record::ilnp::ilnp64_impl;
+
+ilnp64_impl!(
+    /// An L64 record
+    L64Record, L64, "L64",
+    /// The preference of the locator, lowest first
+    preference,
+    /// The 64-bit ILNPv6 locator, the network prefix of an IPv6 subnet
+    locator,
+);
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::L64(L64Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            0x2001_0db8_1140_1000,
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::L64(L64Record::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            0x2001_0db8_1140_1000,
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    question::Question,
+    record::{RecordClass, RecordData, RecordType},
+    resolver::ResolveType,
+    text::{DomainName, HostName},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneReader},
+};
+
+/// An LP record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LpRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    preference: u16,
+    fqdn: HostName,
+}
+
+impl LpRecord {
+    /// Constructs a new LP record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        preference: u16,
+        fqdn: HostName,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            preference,
+            fqdn,
+        }
+    }
+
+    /// The preference of the name, lowest first
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// The name holding the L32 or L64 records of the subnet the owner is on
+    pub fn fqdn(&self) -> &HostName {
+        &self.fqdn
+    }
+}
+
+impl<'read> RecordData<'read> for LpRecord {
+    fn data_size(&self) -> usize {
+        self.fqdn.size() + 2
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        self.preference.encode(writer)?;
+        self.fqdn.encode(writer)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Lp);
+
+        let preference = u16::decode(reader)?;
+        let fqdn = HostName::decode(reader)?;
+
+        if fqdn.size() + 2 != len as usize {
+            return Err(WireError::InvalidLength {
+                expected: fqdn.size(),
+                actual: len as usize,
+            });
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            preference,
+            fqdn,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Lp);
+
+        let preference = reader.read_parsable()?;
+        reader.read_blank()?;
+        let fqdn = reader.read_name()?.into();
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            preference,
+            fqdn,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Lp
+    }
+
+    fn additionals(&self, _: &Question) -> Vec<(Question, ResolveType)> {
+        vec![
+            (
+                Question::new(self.fqdn.clone().into(), self.rclass, RecordType::L32),
+                ResolveType::Additional,
+            ),
+            (
+                Question::new(self.fqdn.clone().into(), self.rclass, RecordType::L64),
+                ResolveType::Additional,
+            ),
+        ]
+    }
+}
+
+impl Display for LpRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.preference, self.fqdn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record};
+
+    #[test]
+    fn wire() {
+        let record = Record::Lp(LpRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            "l64-subnet.example.com.".parse().unwrap(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Lp(LpRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            "l64-subnet.example.com.".parse().unwrap(),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    question::Question,
+    record::{RecordClass, RecordData, RecordType},
+    resolver::ResolveType,
+    text::{DomainName, HostName, Name, Text},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneReader},
+};
+
+/// A NAPTR record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NaptrRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    order: u16,
+    preference: u16,
+    flags: Text,
+    services: Text,
+    regexp: Text,
+    replacement: HostName,
+}
+
+impl NaptrRecord {
+    /// Constructs a new NAPTR record
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        order: u16,
+        preference: u16,
+        flags: Text,
+        services: Text,
+        regexp: Text,
+        replacement: HostName,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            order,
+            preference,
+            flags,
+            services,
+            regexp,
+            replacement,
+        }
+    }
+
+    /// The order the rules must be processed in, lowest first
+    pub fn order(&self) -> u16 {
+        self.order
+    }
+
+    /// The preference between rules of the same order, lowest first
+    pub fn preference(&self) -> u16 {
+        self.preference
+    }
+
+    /// The flags controlling the rewriting, such as `S`, `A`, `U` and `P`
+    pub fn flags(&self) -> &Text {
+        &self.flags
+    }
+
+    /// The services available down this rewrite path
+    pub fn services(&self) -> &Text {
+        &self.services
+    }
+
+    /// The substitution expression applied to the original string
+    pub fn regexp(&self) -> &Text {
+        &self.regexp
+    }
+
+    /// The next name to query for, the root if the regular expression is used instead
+    pub fn replacement(&self) -> &HostName {
+        &self.replacement
+    }
+
+    /// Whether the flags contain a given flag, ignoring case
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.flags
+            .as_bytes()
+            .iter()
+            .any(|byte| byte.eq_ignore_ascii_case(&flag))
+    }
+}
+
+impl<'read> RecordData<'read> for NaptrRecord {
+    fn data_size(&self) -> usize {
+        4 + self.flags.size() + self.services.size() + self.regexp.size() + self.replacement.size()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        self.order.encode(writer)?;
+        self.preference.encode(writer)?;
+        self.flags.encode(writer)?;
+        self.services.encode(writer)?;
+        self.regexp.encode(writer)?;
+        self.replacement.encode(writer)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Naptr);
+
+        let order = u16::decode(reader)?;
+        let preference = u16::decode(reader)?;
+        let flags = Text::decode(reader)?;
+        let services = Text::decode(reader)?;
+        let regexp = Text::decode(reader)?;
+        let replacement = HostName::decode(reader)?;
+
+        let record = Self {
+            name,
+            ttl,
+            rclass,
+            order,
+            preference,
+            flags,
+            services,
+            regexp,
+            replacement,
+        };
+
+        if record.data_size() != len as usize {
+            return Err(WireError::InvalidLength {
+                expected: record.data_size(),
+                actual: len as usize,
+            });
+        }
+
+        Ok(record)
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Naptr);
+
+        let order = reader.read_parsable()?;
+        reader.read_blank()?;
+        let preference = reader.read_parsable()?;
+        reader.read_blank()?;
+        let flags = reader.read_text()?;
+        reader.read_blank()?;
+        let services = reader.read_text()?;
+        reader.read_blank()?;
+        let regexp = reader.read_text()?;
+        reader.read_blank()?;
+        let replacement = reader.read_name()?.into();
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            order,
+            preference,
+            flags,
+            services,
+            regexp,
+            replacement,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Naptr
+    }
+
+    fn additionals(&self, _: &Question) -> Vec<(Question, ResolveType)> {
+        if self.replacement.labels().is_empty() {
+            return Vec::new();
+        }
+
+        // Terminal flags say what the replacement names, other rules lead to more NAPTR records.
+        let rtypes: &[RecordType] = if self.has_flag(b'S') {
+            &[RecordType::Srv]
+        } else if self.has_flag(b'A') {
+            &[RecordType::A, RecordType::Aaaa]
+        } else {
+            &[]
+        };
+
+        rtypes
+            .iter()
+            .map(|rtype| {
+                (
+                    Question::new(self.replacement.clone().into(), self.rclass, *rtype),
+                    ResolveType::Additional,
+                )
+            })
+            .collect()
+    }
+}
+
+impl Display for NaptrRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.order, self.preference, self.flags, self.services, self.regexp, self.replacement,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::record::{round_trip, Record};
+
+    fn record(flags: &str, services: &str, regexp: &str, replacement: &str) -> NaptrRecord {
+        NaptrRecord::new(
+            "example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            100,
+            10,
+            flags.as_bytes().to_vec().into(),
+            services.as_bytes().to_vec().into(),
+            regexp.as_bytes().to_vec().into(),
+            replacement.parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn wire() {
+        let record = Record::Naptr(record("S", "SIP+D2U", "", "_sip._udp.example.com."));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Naptr(record("u", "E2U+sip", "!^.*$!sip:info@example.com!", "."));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case("S", "_sip._udp.example.com." => vec![RecordType::Srv]; "srv")]
+    #[test_case("a", "sip.example.com." => vec![RecordType::A, RecordType::Aaaa]; "address")]
+    #[test_case("", "_sip.example.com." => Vec::<RecordType>::new(); "non terminal")]
+    #[test_case("S", "." => Vec::<RecordType>::new(); "no replacement")]
+    fn additionals(flags: &str, replacement: &str) -> Vec<RecordType> {
+        let record = record(flags, "SIP+D2U", "", replacement);
+        let question = Question::new(
+            "example.com.".parse().unwrap(),
+            RecordClass::In,
+            RecordType::Naptr,
+        );
+
+        record
+            .additionals(&question)
+            .into_iter()
+            .map(|(question, _)| question.qtype())
+            .collect()
+    }
+}
@@ -0,0 +1,45 @@
+use crate::record::ilnp::ilnp64_impl;
+
+ilnp64_impl!(
+    /// An NID record
+    NidRecord, Nid, "NID",
+    /// The preference of the node identifier, lowest first
+    preference,
+    /// The 64-bit ILNPv6 node identifier, the interface identifier part of an IPv6 address
+    node_id,
+);
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::Nid(NidRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            0x0014_4fff_ff20_ee64,
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Nid(NidRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            0x0014_4fff_ff20_ee64,
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
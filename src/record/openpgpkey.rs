@@ -0,0 +1,161 @@
+use std::fmt::{self, Display, Formatter};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::DomainName,
+    wire::{WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader},
+};
+
+/// An OPENPGPKEY record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenpgpkeyRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    key: Vec<u8>,
+}
+
+impl OpenpgpkeyRecord {
+    /// Constructs a new OPENPGPKEY record
+    pub fn new(name: DomainName, ttl: u32, rclass: RecordClass, key: Vec<u8>) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            key,
+        }
+    }
+
+    /// The transferable public key of the owner
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl<'read> RecordData<'read> for OpenpgpkeyRecord {
+    fn data_size(&self) -> usize {
+        self.key.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        writer.write(&self.key)
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Openpgpkey);
+
+        let mut key = vec![0; len as usize];
+        reader.read(&mut key)?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            key,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Openpgpkey);
+
+        let key = reader.read_base64()?;
+
+        if key.is_empty() {
+            return reader.error(ZoneErrorKind::BadEntry);
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            key,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Openpgpkey
+    }
+}
+
+impl Display for OpenpgpkeyRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", BASE64.encode(&self.key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::{
+        record::{round_trip, Record},
+        zone::read_zone,
+    };
+
+    const OWNER: &str =
+        "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._openpgpkey.example.com.";
+
+    #[test]
+    fn wire() {
+        let record = Record::Openpgpkey(OpenpgpkeyRecord::new(
+            OWNER.parse().unwrap(),
+            3600,
+            RecordClass::In,
+            b"\x99\x01\x0d\x04".to_vec(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Openpgpkey(OpenpgpkeyRecord::new(
+            OWNER.parse().unwrap(),
+            3600,
+            RecordClass::In,
+            b"\x99\x01\x0d\x04".to_vec(),
+        ));
+
+        assert_display_snapshot!(record);
+
+        round_trip::zone(&record);
+
+        // Keys are usually split over several lines.
+        assert_eq!(
+            read_zone(
+                &format!("{} 3600 IN OPENPGPKEY ( mQ\n  EN BA== )", OWNER),
+                Vec::new().into()
+            ),
+            read_zone(&record.to_string(), Vec::new().into()),
+        );
+    }
+}
@@ -160,9 +160,9 @@ mod tests {
             ".".parse().unwrap(),
             1312,
             0b0000_0000_0000_0000_1000_0000_0000_0000,
-            &[Opt::NameServerIdentifier(NameServerIdentifierOpt::new(&[
-                b'r', b'e', b'a', b'l', b'm',
-            ]))],
+            &[Opt::NameServerIdentifier(NameServerIdentifierOpt::new(
+                b"realm",
+            ))],
         ));
 
         let wire = to_wire(&record).unwrap();
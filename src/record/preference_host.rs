@@ -0,0 +1,164 @@
+/// Implements a record type holding a 16-bit preference and a host name, sharing the data
+/// format of MX. The address records of the host are resolved as additionals.
+macro_rules! preference_host_impl {
+    (
+        $(#[$doc:meta])* $record:ident, $rtype:ident, $name:literal,
+        $(#[$preference_doc:meta])* $preference:ident,
+        $(#[$host_doc:meta])* $host:ident $(,)?
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $record {
+            name: $crate::text::DomainName,
+            ttl: u32,
+            rclass: $crate::record::RecordClass,
+            $preference: u16,
+            $host: $crate::text::HostName,
+        }
+
+        impl $record {
+            #[doc = concat!("Constructs a new ", $name, " record")]
+            pub fn new(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                $preference: u16,
+                $host: $crate::text::HostName,
+            ) -> Self {
+                Self {
+                    name,
+                    ttl,
+                    rclass,
+                    $preference,
+                    $host,
+                }
+            }
+
+            $(#[$preference_doc])*
+            pub fn $preference(&self) -> u16 {
+                self.$preference
+            }
+
+            $(#[$host_doc])*
+            pub fn $host(&self) -> &$crate::text::HostName {
+                &self.$host
+            }
+        }
+
+        impl<'read> $crate::record::RecordData<'read> for $record {
+            fn data_size(&self) -> usize {
+                use $crate::wire::WireEncode;
+
+                self.$host.size() + 2
+            }
+
+            fn encode_data(
+                &self,
+                writer: &mut $crate::wire::WireWrite,
+            ) -> Result<(), $crate::wire::WireError> {
+                use $crate::wire::WireEncode;
+
+                self.$preference.encode(writer)?;
+                self.$host.encode(writer)?;
+
+                Ok(())
+            }
+
+            fn decode_data(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                len: u16,
+                reader: &mut $crate::wire::WireRead<'read>,
+            ) -> Result<Self, $crate::wire::WireError> {
+                use $crate::wire::{WireDecode, WireEncode};
+
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                let $preference = u16::decode(reader)?;
+                let $host = $crate::text::HostName::decode(reader)?;
+
+                if $host.size() + 2 != len as usize {
+                    return Err($crate::wire::WireError::InvalidLength {
+                        expected: $host.size(),
+                        actual: len as usize,
+                    });
+                }
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    $preference,
+                    $host,
+                })
+            }
+
+            fn decode_zone(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                reader: &mut $crate::zone::ZoneReader,
+            ) -> Result<Self, $crate::zone::ZoneError> {
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                let $preference = reader.read_parsable()?;
+                reader.read_blank()?;
+                let $host = reader.read_name()?.into();
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    $preference,
+                    $host,
+                })
+            }
+
+            fn name(&self) -> &$crate::text::DomainName {
+                &self.name
+            }
+
+            fn ttl(&self) -> u32 {
+                self.ttl
+            }
+
+            fn rclass(&self) -> $crate::record::RecordClass {
+                self.rclass
+            }
+
+            fn rtype(&self) -> $crate::record::RecordType {
+                $crate::record::RecordType::$rtype
+            }
+
+            fn additionals(
+                &self,
+                _: &$crate::question::Question,
+            ) -> Vec<($crate::question::Question, $crate::resolver::ResolveType)> {
+                [$crate::record::RecordType::A, $crate::record::RecordType::Aaaa]
+                    .into_iter()
+                    .map(|rtype| {
+                        (
+                            $crate::question::Question::new(
+                                self.$host.clone().into(),
+                                self.rclass,
+                                rtype,
+                            ),
+                            $crate::resolver::ResolveType::Additional,
+                        )
+                    })
+                    .collect()
+            }
+        }
+
+        impl std::fmt::Display for $record {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{} {}", self.$preference, self.$host)
+            }
+        }
+    };
+}
+
+pub(crate) use preference_host_impl;
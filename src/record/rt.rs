@@ -0,0 +1,45 @@
+use crate::record::preference_host::preference_host_impl;
+
+preference_host_impl!(
+    /// An RT record
+    RtRecord, Rt, "RT",
+    /// The preference of the intermediate host, lowest first
+    preference,
+    /// The host to route through
+    intermediate,
+);
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::Rt(RtRecord::new(
+            "example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            "relay.example.com.".parse().unwrap(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Rt(RtRecord::new(
+            "example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            "relay.example.com.".parse().unwrap(),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
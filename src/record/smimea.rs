@@ -0,0 +1,46 @@
+use crate::record::tlsa::tlsa_impl;
+
+tlsa_impl!(SmimeaRecord, Smimea, "SMIMEA");
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, tlsa::MATCHING_SHA512, Record, RecordClass};
+
+    #[test]
+    fn wire() {
+        let record = Record::Smimea(SmimeaRecord::new(
+            "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._smimecert.example.com."
+                .parse()
+                .unwrap(),
+            3600,
+            RecordClass::In,
+            3,
+            0,
+            MATCHING_SHA512,
+            vec![0xab; 64],
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Smimea(SmimeaRecord::new(
+            "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._smimecert.example.com."
+                .parse()
+                .unwrap(),
+            3600,
+            RecordClass::In,
+            3,
+            0,
+            MATCHING_SHA512,
+            vec![0xab; 64],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::DomainName,
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader},
+};
+
+/// An SSHFP record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshfpRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    algorithm: u8,
+    fingerprint_type: u8,
+    fingerprint: Vec<u8>,
+}
+
+impl SshfpRecord {
+    /// The fingerprint type of a SHA-1 hash
+    pub const SHA1: u8 = 1;
+
+    /// The fingerprint type of a SHA-256 hash
+    pub const SHA256: u8 = 2;
+
+    /// Constructs a new SSHFP record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        algorithm: u8,
+        fingerprint_type: u8,
+        fingerprint: Vec<u8>,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            algorithm,
+            fingerprint_type,
+            fingerprint,
+        }
+    }
+
+    /// The algorithm of the public key, such as RSA or Ed25519
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    /// The hash used to produce the fingerprint
+    pub fn fingerprint_type(&self) -> u8 {
+        self.fingerprint_type
+    }
+
+    /// The fingerprint of the public key
+    pub fn fingerprint(&self) -> &[u8] {
+        &self.fingerprint
+    }
+}
+
+impl<'read> RecordData<'read> for SshfpRecord {
+    fn data_size(&self) -> usize {
+        2 + self.fingerprint.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        self.algorithm.encode(writer)?;
+        self.fingerprint_type.encode(writer)?;
+        writer.write(&self.fingerprint)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Sshfp);
+
+        if len < 2 {
+            return Err(WireError::InvalidLength {
+                expected: 2,
+                actual: len as usize,
+            });
+        }
+
+        let algorithm = u8::decode(reader)?;
+        let fingerprint_type = u8::decode(reader)?;
+
+        let mut fingerprint = vec![0; len as usize - 2];
+        reader.read(&mut fingerprint)?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            algorithm,
+            fingerprint_type,
+            fingerprint,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Sshfp);
+
+        let algorithm = reader.read_parsable()?;
+        reader.read_blank()?;
+        let fingerprint_type = reader.read_parsable()?;
+        reader.read_blank()?;
+        let fingerprint = reader.read_hex()?;
+
+        let valid = match fingerprint_type {
+            Self::SHA1 => fingerprint.len() == 20,
+            Self::SHA256 => fingerprint.len() == 32,
+            _ => !fingerprint.is_empty(),
+        };
+
+        if !valid {
+            return reader.error(ZoneErrorKind::BadEntry);
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            algorithm,
+            fingerprint_type,
+            fingerprint,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Sshfp
+    }
+}
+
+impl Display for SshfpRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.algorithm,
+            self.fingerprint_type,
+            hex::encode(&self.fingerprint),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        record::{round_trip, Record},
+        zone::read_zone,
+    };
+
+    #[test]
+    fn wire() {
+        let record = Record::Sshfp(SshfpRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            4,
+            SshfpRecord::SHA1,
+            vec![0x12; 20],
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Sshfp(SshfpRecord::new(
+            "host.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            4,
+            SshfpRecord::SHA256,
+            vec![0xab; 32],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case("1 1 dd465c09cfa51fb45020cc83316fff21b9ec74ac" => true; "sha1")]
+    #[test_case("1 1 dd465c09cfa51fb45020cc83316fff21b9ec74" => false; "sha1 short")]
+    #[test_case("1 2 dd465c09cfa51fb45020cc83316fff21b9ec74ac" => false; "sha256 short")]
+    #[test_case("1 3 dd465c09" => true; "unknown type")]
+    #[test_case("1 2" => false; "missing fingerprint")]
+    fn zone_fingerprint(rdata: &str) -> bool {
+        read_zone(
+            &format!("host.example.com. 3600 IN SSHFP {}", rdata),
+            Vec::new().into(),
+        )
+        .is_ok()
+    }
+}
@@ -0,0 +1,883 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use enum_other::other;
+
+use crate::{
+    text::{parse_text, write_quoted, TextParseResult},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader, ZoneToken},
+};
+
+/// The key of a service parameter
+#[other(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvcParamKey {
+    Mandatory = 0,
+    Alpn = 1,
+    NoDefaultAlpn = 2,
+    Port = 3,
+    Ipv4Hint = 4,
+    Ech = 5,
+    Ipv6Hint = 6,
+}
+
+impl Display for SvcParamKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Mandatory => write!(f, "mandatory"),
+            Self::Alpn => write!(f, "alpn"),
+            Self::NoDefaultAlpn => write!(f, "no-default-alpn"),
+            Self::Port => write!(f, "port"),
+            Self::Ipv4Hint => write!(f, "ipv4hint"),
+            Self::Ech => write!(f, "ech"),
+            Self::Ipv6Hint => write!(f, "ipv6hint"),
+            Self::Other(key) => write!(f, "key{}", key),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseSvcParamKeyError;
+
+impl Display for ParseSvcParamKeyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "provided string was not a recognised service parameter key"
+        )
+    }
+}
+
+impl Error for ParseSvcParamKeyError {}
+
+impl FromStr for SvcParamKey {
+    type Err = ParseSvcParamKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(digits) = s.strip_prefix("key") {
+            // The last key is reserved as an "invalid key".
+            return match digits.parse::<u16>() {
+                Ok(key) if key != u16::MAX => Ok(Self::from(key)),
+                _ => Err(ParseSvcParamKeyError),
+            };
+        }
+
+        match s {
+            "mandatory" => Ok(Self::Mandatory),
+            "alpn" => Ok(Self::Alpn),
+            "no-default-alpn" => Ok(Self::NoDefaultAlpn),
+            "port" => Ok(Self::Port),
+            "ipv4hint" => Ok(Self::Ipv4Hint),
+            "ech" => Ok(Self::Ech),
+            "ipv6hint" => Ok(Self::Ipv6Hint),
+            _ => Err(ParseSvcParamKeyError),
+        }
+    }
+}
+
+/// A service parameter of an SVCB or HTTPS record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SvcParam {
+    Mandatory(Vec<SvcParamKey>),
+    Alpn(Vec<Vec<u8>>),
+    NoDefaultAlpn,
+    Port(u16),
+    Ipv4Hint(Vec<Ipv4Addr>),
+    Ech(Vec<u8>),
+    Ipv6Hint(Vec<Ipv6Addr>),
+    Other(u16, Vec<u8>),
+}
+
+impl SvcParam {
+    /// The key of the parameter
+    pub fn key(&self) -> SvcParamKey {
+        match self {
+            Self::Mandatory(_) => SvcParamKey::Mandatory,
+            Self::Alpn(_) => SvcParamKey::Alpn,
+            Self::NoDefaultAlpn => SvcParamKey::NoDefaultAlpn,
+            Self::Port(_) => SvcParamKey::Port,
+            Self::Ipv4Hint(_) => SvcParamKey::Ipv4Hint,
+            Self::Ech(_) => SvcParamKey::Ech,
+            Self::Ipv6Hint(_) => SvcParamKey::Ipv6Hint,
+            Self::Other(key, _) => SvcParamKey::Other(*key),
+        }
+    }
+
+    fn value_size(&self) -> usize {
+        match self {
+            Self::Mandatory(keys) => keys.len() * 2,
+            Self::Alpn(ids) => ids.iter().map(|id| id.len() + 1).sum(),
+            Self::NoDefaultAlpn => 0,
+            Self::Port(_) => 2,
+            Self::Ipv4Hint(addrs) => addrs.len() * 4,
+            Self::Ech(config) => config.len(),
+            Self::Ipv6Hint(addrs) => addrs.len() * 16,
+            Self::Other(_, value) => value.len(),
+        }
+    }
+
+    /// Parses the value of a parameter from its wire format
+    fn from_wire_value(key: SvcParamKey, value: Vec<u8>) -> Result<Self, WireError> {
+        fn chunks(value: &[u8], size: usize) -> Result<std::slice::ChunksExact<'_, u8>, WireError> {
+            if value.is_empty() || !value.len().is_multiple_of(size) {
+                return Err(WireError::InvalidLength {
+                    expected: value.len() / size * size + size,
+                    actual: value.len(),
+                });
+            }
+
+            Ok(value.chunks_exact(size))
+        }
+
+        match key {
+            SvcParamKey::Mandatory => {
+                let keys = chunks(&value, 2)?
+                    .map(|key| u16::from_be_bytes([key[0], key[1]]))
+                    .collect::<Vec<_>>();
+
+                // The keys must be in strictly increasing order, which also rules out duplicates.
+                match keys.windows(2).all(|pair| pair[0] < pair[1]) {
+                    true => Ok(Self::Mandatory(
+                        keys.into_iter().map(SvcParamKey::from).collect(),
+                    )),
+                    false => Err(WireError::UnsupportedFormat),
+                }
+            }
+            SvcParamKey::Alpn => {
+                let mut reader = WireRead::new(&value);
+                let mut ids = Vec::new();
+
+                while reader.pos() != value.len() {
+                    let mut id = vec![0; u8::decode(&mut reader)? as usize];
+                    reader.read(&mut id)?;
+
+                    if id.is_empty() {
+                        return Err(WireError::UnsupportedFormat);
+                    }
+                    ids.push(id);
+                }
+
+                match ids.is_empty() {
+                    true => Err(WireError::UnsupportedFormat),
+                    false => Ok(Self::Alpn(ids)),
+                }
+            }
+            SvcParamKey::NoDefaultAlpn => match value.len() {
+                0 => Ok(Self::NoDefaultAlpn),
+                len => Err(WireError::InvalidLength {
+                    expected: 0,
+                    actual: len,
+                }),
+            },
+            SvcParamKey::Port => match value[..] {
+                [high, low] => Ok(Self::Port(u16::from_be_bytes([high, low]))),
+                _ => Err(WireError::InvalidLength {
+                    expected: 2,
+                    actual: value.len(),
+                }),
+            },
+            SvcParamKey::Ipv4Hint => Ok(Self::Ipv4Hint(
+                chunks(&value, 4)?
+                    .map(|addr| Ipv4Addr::new(addr[0], addr[1], addr[2], addr[3]))
+                    .collect(),
+            )),
+            SvcParamKey::Ech => Ok(Self::Ech(value)),
+            SvcParamKey::Ipv6Hint => Ok(Self::Ipv6Hint(
+                chunks(&value, 16)?
+                    .map(|addr| Ipv6Addr::from(<[u8; 16]>::try_from(addr).unwrap()))
+                    .collect(),
+            )),
+            SvcParamKey::Other(key) => Ok(Self::Other(key, value)),
+        }
+    }
+
+    /// Parses the value of a parameter from its presentation format, with escapes already
+    /// removed. Returns `None` if the value is malformed.
+    fn from_zone_value(key: SvcParamKey, value: Option<Vec<u8>>) -> Option<Self> {
+        fn parse_list<T: FromStr>(value: &[u8]) -> Option<Vec<T>> {
+            value
+                .split(|byte| *byte == b',')
+                .map(|item| std::str::from_utf8(item).ok()?.parse().ok())
+                .collect()
+        }
+
+        // Only opaque keys and no-default-alpn may go without a value.
+        let value = match (key, value) {
+            (SvcParamKey::NoDefaultAlpn, None) => return Some(Self::NoDefaultAlpn),
+            (SvcParamKey::Other(key), value) => {
+                return Some(Self::Other(key, value.unwrap_or_default()))
+            }
+            (_, value) => value.filter(|value| !value.is_empty())?,
+        };
+
+        match key {
+            SvcParamKey::Mandatory => {
+                let mut keys = parse_list(&value)?;
+                sort_keys(&mut keys);
+
+                // A key may only be listed once.
+                match keys.windows(2).any(|pair| pair[0] == pair[1]) {
+                    true => None,
+                    false => Some(Self::Mandatory(keys)),
+                }
+            }
+            SvcParamKey::Alpn => {
+                let ids = split_value_list(&value)?;
+
+                match ids.iter().any(|id| id.is_empty() || id.len() > 255) {
+                    true => None,
+                    false => Some(Self::Alpn(ids)),
+                }
+            }
+            SvcParamKey::Port => std::str::from_utf8(&value)
+                .ok()?
+                .parse()
+                .ok()
+                .map(Self::Port),
+            SvcParamKey::Ipv4Hint => parse_list(&value).map(Self::Ipv4Hint),
+            SvcParamKey::Ech => BASE64.decode(&value).ok().map(Self::Ech),
+            SvcParamKey::Ipv6Hint => parse_list(&value).map(Self::Ipv6Hint),
+            SvcParamKey::NoDefaultAlpn | SvcParamKey::Other(_) => None,
+        }
+    }
+}
+
+impl WireEncode for SvcParam {
+    fn size(&self) -> usize {
+        4 + self.value_size()
+    }
+
+    fn encode(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        u16::from(self.key()).encode(writer)?;
+        (self.value_size() as u16).encode(writer)?;
+
+        match self {
+            Self::Mandatory(keys) => {
+                for key in keys {
+                    u16::from(*key).encode(writer)?;
+                }
+            }
+            Self::Alpn(ids) => {
+                for id in ids {
+                    (id.len() as u8).encode(writer)?;
+                    writer.write(id)?;
+                }
+            }
+            Self::NoDefaultAlpn => (),
+            Self::Port(port) => port.encode(writer)?,
+            Self::Ipv4Hint(addrs) => {
+                for addr in addrs {
+                    u32::from(*addr).encode(writer)?;
+                }
+            }
+            Self::Ech(config) => writer.write(config)?,
+            Self::Ipv6Hint(addrs) => {
+                for addr in addrs {
+                    u128::from(*addr).encode(writer)?;
+                }
+            }
+            Self::Other(_, value) => writer.write(value)?,
+        }
+
+        Ok(())
+    }
+}
+
+impl<'read> WireDecode<'read> for SvcParam {
+    fn decode(reader: &mut WireRead<'read>) -> Result<Self, WireError> {
+        let key = SvcParamKey::from(u16::decode(reader)?);
+        let mut value = vec![0; u16::decode(reader)? as usize];
+        reader.read(&mut value)?;
+
+        Self::from_wire_value(key, value)
+    }
+}
+
+impl Display for SvcParam {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fn join<T: Display>(items: &[T]) -> String {
+            items
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+
+        write!(f, "{}", self.key())?;
+
+        match self {
+            Self::Mandatory(keys) => write!(f, "={}", join(keys)),
+            Self::Alpn(ids) => {
+                write!(f, "=")?;
+                write_quoted(f, &join_value_list(ids))
+            }
+            Self::NoDefaultAlpn => Ok(()),
+            Self::Port(port) => write!(f, "={}", port),
+            Self::Ipv4Hint(addrs) => write!(f, "={}", join(addrs)),
+            Self::Ech(config) => write!(f, "={}", BASE64.encode(config)),
+            Self::Ipv6Hint(addrs) => write!(f, "={}", join(addrs)),
+            Self::Other(_, value) if value.is_empty() => Ok(()),
+            Self::Other(_, value) => {
+                write!(f, "=")?;
+                write_quoted(f, value)
+            }
+        }
+    }
+}
+
+/// Checks that parameters are in strictly increasing key order, and that the keys listed by
+/// `mandatory` are present. Parameters that depend on another, like `no-default-alpn`
+/// depending on `alpn`, are also checked.
+fn validate_params(params: &[SvcParam]) -> bool {
+    let ordered = params
+        .windows(2)
+        .all(|pair| u16::from(pair[0].key()) < u16::from(pair[1].key()));
+
+    let has = |key| params.iter().any(|param| param.key() == key);
+
+    let mandatory = params.iter().all(|param| match param {
+        SvcParam::Mandatory(keys) => {
+            keys.iter()
+                .all(|key| *key != SvcParamKey::Mandatory && has(*key))
+                && keys
+                    .windows(2)
+                    .all(|pair| u16::from(pair[0]) < u16::from(pair[1]))
+        }
+        _ => true,
+    });
+
+    ordered && mandatory && (!has(SvcParamKey::NoDefaultAlpn) || has(SvcParamKey::Alpn))
+}
+
+/// Sorts parameters into wire order, by their keys, along with the keys listed by `mandatory`.
+pub(crate) fn sort_params(params: &mut [SvcParam]) {
+    params.sort_by_key(|param| u16::from(param.key()));
+
+    for param in params {
+        if let SvcParam::Mandatory(keys) = param {
+            sort_keys(keys);
+        }
+    }
+}
+
+/// Sorts the keys listed by `mandatory` into wire order.
+fn sort_keys(keys: &mut [SvcParamKey]) {
+    keys.sort_by_key(|key| u16::from(*key));
+}
+
+/// Splits a comma separated value list, where items escape commas and backslashes with a
+/// backslash as described in RFC 9460 appendix A.1. Returns `None` on a trailing backslash.
+fn split_value_list(value: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let mut items = vec![Vec::new()];
+    let mut bytes = value.iter();
+
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'\\' => items.last_mut().unwrap().push(*bytes.next()?),
+            b',' => items.push(Vec::new()),
+            _ => items.last_mut().unwrap().push(*byte),
+        }
+    }
+
+    Some(items)
+}
+
+/// Joins items into a comma separated value list, escaping commas and backslashes within them.
+fn join_value_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut value = Vec::new();
+
+    for (i, item) in items.iter().enumerate() {
+        if i != 0 {
+            value.push(b',');
+        }
+
+        for byte in item {
+            if matches!(byte, b',' | b'\\') {
+                value.push(b'\\');
+            }
+            value.push(*byte);
+        }
+    }
+
+    value
+}
+
+/// Decodes the parameters making up the last `len` bytes of the record data.
+pub(crate) fn decode_params(len: usize, reader: &mut WireRead) -> Result<Vec<SvcParam>, WireError> {
+    let end = reader.pos() + len;
+    let mut params = Vec::new();
+
+    while reader.pos() < end {
+        params.push(SvcParam::decode(reader)?);
+    }
+
+    if reader.pos() != end {
+        return Err(WireError::InvalidLength {
+            expected: len,
+            actual: len + reader.pos() - end,
+        });
+    }
+
+    match validate_params(&params) {
+        true => Ok(params),
+        false => Err(WireError::UnsupportedFormat),
+    }
+}
+
+/// Reads the parameters in the remainder of a zone entry, each either `key`, `key=value`
+/// or `key="value"`.
+pub(crate) fn read_params(reader: &mut ZoneReader) -> Result<Vec<SvcParam>, ZoneError> {
+    let mut params = Vec::new();
+
+    loop {
+        match reader.read_blank() {
+            Ok(_) => {
+                if reader.peek().is_none() {
+                    break;
+                }
+            }
+            Err(err) => match err.kind() {
+                ZoneErrorKind::IncompleteEntry => break,
+                _ => return Err(err),
+            },
+        }
+
+        let param = reader.read_string()?;
+        let (key, value) = match param.split_once('=') {
+            Some((key, "")) => match reader.peek() {
+                Some(ZoneToken::Text(_)) => (key, Some(reader.read_text()?.into())),
+                _ => (key, Some(Vec::new())),
+            },
+            Some((key, value)) => match parse_text(value, '"', false) {
+                TextParseResult::EndOfString(_, value) => (key, Some(value)),
+                TextParseResult::UnknownEscape(sequence) => {
+                    return reader.error(ZoneErrorKind::UnknownEscape(sequence));
+                }
+                _ => return reader.error(ZoneErrorKind::BadEntry),
+            },
+            None => (param.as_str(), None),
+        };
+
+        let Ok(key) = key.parse() else {
+            return reader.error(ZoneErrorKind::BadEntry);
+        };
+        let Some(param) = SvcParam::from_zone_value(key, value) else {
+            return reader.error(ZoneErrorKind::BadEntry);
+        };
+
+        params.push(param);
+    }
+
+    sort_params(&mut params);
+
+    match validate_params(&params) {
+        true => Ok(params),
+        false => reader.error(ZoneErrorKind::BadEntry),
+    }
+}
+
+/// Implements a record type with the SVCB data format, a priority, a target name and a list of
+/// service parameters.
+macro_rules! svcb_impl {
+    ($record:ident, $rtype:ident, $doc:literal) => {
+        #[doc = concat!("An ", $doc, " record")]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $record {
+            name: $crate::text::DomainName,
+            ttl: u32,
+            rclass: $crate::record::RecordClass,
+            priority: u16,
+            target: $crate::text::HostName,
+            params: Vec<$crate::record::svcb::SvcParam>,
+        }
+
+        impl $record {
+            #[doc = concat!("Constructs a new ", $doc, " record, the parameters are sorted by key")]
+            pub fn new(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                priority: u16,
+                target: $crate::text::HostName,
+                mut params: Vec<$crate::record::svcb::SvcParam>,
+            ) -> Self {
+                $crate::record::svcb::sort_params(&mut params);
+
+                Self {
+                    name,
+                    ttl,
+                    rclass,
+                    priority,
+                    target,
+                    params,
+                }
+            }
+
+            /// The priority of the service, zero marks an alias
+            pub fn priority(&self) -> u16 {
+                self.priority
+            }
+
+            /// Whether the record is in alias mode, only naming another owner of the service
+            pub fn is_alias(&self) -> bool {
+                self.priority == 0
+            }
+
+            /// The host of the service, the root means the owner of the record itself
+            pub fn target(&self) -> &$crate::text::HostName {
+                &self.target
+            }
+
+            /// The service parameters, in key order
+            pub fn params(&self) -> &[$crate::record::svcb::SvcParam] {
+                &self.params
+            }
+
+            /// Gets a service parameter by its key
+            pub fn param(
+                &self,
+                key: $crate::record::svcb::SvcParamKey,
+            ) -> Option<&$crate::record::svcb::SvcParam> {
+                self.params.iter().find(|param| param.key() == key)
+            }
+        }
+
+        impl<'read> $crate::record::RecordData<'read> for $record {
+            fn data_size(&self) -> usize {
+                use $crate::wire::WireEncode;
+
+                2 + self.target.size() + self.params.iter().map(WireEncode::size).sum::<usize>()
+            }
+
+            fn encode_data(
+                &self,
+                writer: &mut $crate::wire::WireWrite,
+            ) -> Result<(), $crate::wire::WireError> {
+                use $crate::wire::WireEncode;
+
+                self.priority.encode(writer)?;
+                self.target.encode(writer)?;
+                for param in &self.params {
+                    param.encode(writer)?;
+                }
+
+                Ok(())
+            }
+
+            fn decode_data(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                len: u16,
+                reader: &mut $crate::wire::WireRead<'read>,
+            ) -> Result<Self, $crate::wire::WireError> {
+                use $crate::wire::{WireDecode, WireEncode};
+
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                let priority = u16::decode(reader)?;
+                let target = $crate::text::HostName::decode(reader)?;
+
+                if 2 + target.size() > len as usize {
+                    return Err($crate::wire::WireError::InvalidLength {
+                        expected: 2 + target.size(),
+                        actual: len as usize,
+                    });
+                }
+
+                let params =
+                    $crate::record::svcb::decode_params(len as usize - 2 - target.size(), reader)?;
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    priority,
+                    target,
+                    params,
+                })
+            }
+
+            fn decode_zone(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                reader: &mut $crate::zone::ZoneReader,
+            ) -> Result<Self, $crate::zone::ZoneError> {
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                let priority = reader.read_parsable()?;
+                reader.read_blank()?;
+                let target = reader.read_name()?.into();
+                let params = $crate::record::svcb::read_params(reader)?;
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    priority,
+                    target,
+                    params,
+                })
+            }
+
+            fn name(&self) -> &$crate::text::DomainName {
+                &self.name
+            }
+
+            fn ttl(&self) -> u32 {
+                self.ttl
+            }
+
+            fn rclass(&self) -> $crate::record::RecordClass {
+                self.rclass
+            }
+
+            fn rtype(&self) -> $crate::record::RecordType {
+                $crate::record::RecordType::$rtype
+            }
+
+            fn additionals(
+                &self,
+                _: &$crate::question::Question,
+            ) -> Vec<($crate::question::Question, $crate::resolver::ResolveType)> {
+                use $crate::{
+                    question::Question,
+                    record::RecordType,
+                    resolver::ResolveType,
+                    text::{DomainName, Name},
+                };
+
+                // A root target in service mode stands for the owner, in alias mode it means the
+                // service isn't available.
+                let target: DomainName = match self.target.labels().is_empty() {
+                    true if self.is_alias() => return Vec::new(),
+                    true => self.name.clone(),
+                    false => self.target.clone().into(),
+                };
+
+                vec![
+                    (
+                        Question::new(target.clone(), self.rclass, RecordType::A),
+                        ResolveType::Additional,
+                    ),
+                    (
+                        Question::new(target, self.rclass, RecordType::Aaaa),
+                        ResolveType::Additional,
+                    ),
+                ]
+            }
+        }
+
+        impl std::fmt::Display for $record {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{} {}", self.priority, self.target)?;
+                for param in &self.params {
+                    write!(f, " {}", param)?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use svcb_impl;
+
+svcb_impl!(SvcbRecord, Svcb, "SVCB");
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        question::Question,
+        record::{round_trip, Record, RecordClass, RecordData, RecordType},
+        resolver::ResolveType,
+        text::DomainName,
+        wire::{from_wire, to_wire},
+        zone::read_zone,
+    };
+
+    fn record(priority: u16, target: &str, params: Vec<SvcParam>) -> SvcbRecord {
+        SvcbRecord::new(
+            "_dns.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            priority,
+            target.parse().unwrap(),
+            params,
+        )
+    }
+
+    #[test]
+    fn wire() {
+        let record = Record::Svcb(record(
+            1,
+            ".",
+            vec![
+                SvcParam::Port(853),
+                SvcParam::Alpn(vec![b"dot".to_vec()]),
+                SvcParam::Other(65280, b"private".to_vec()),
+            ],
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Svcb(record(
+            16,
+            "foo.example.org.",
+            vec![
+                SvcParam::Mandatory(vec![SvcParamKey::Alpn, SvcParamKey::Ipv4Hint]),
+                SvcParam::Alpn(vec![b"h2".to_vec(), b"h3-19".to_vec()]),
+                SvcParam::Ipv4Hint(vec![Ipv4Addr::new(192, 0, 2, 1)]),
+                SvcParam::Ipv6Hint(vec!["2001:db8::1".parse().unwrap()]),
+                SvcParam::Ech(b"\x00\x45\xfe\x0d".to_vec()),
+            ],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case("1 . alpn=h2,h3 port=443" => Ok(vec![
+        SvcParam::Alpn(vec![b"h2".to_vec(), b"h3".to_vec()]),
+        SvcParam::Port(443),
+    ]); "unquoted")]
+    #[test_case("1 . key65333=ex1 key1=\"h2\" no-default-alpn" => Ok(vec![
+        SvcParam::Alpn(vec![b"h2".to_vec()]),
+        SvcParam::NoDefaultAlpn,
+        SvcParam::Other(65333, b"ex1".to_vec()),
+    ]); "generic keys")]
+    #[test_case("1 . key667=\"hello\\210qoo\"" => Ok(vec![
+        SvcParam::Other(667, b"hello\xd2qoo".to_vec()),
+    ]); "escaped")]
+    #[test_case("1 . mandatory=ipv4hint,alpn alpn=h2 ipv4hint=192.0.2.1" => Ok(vec![
+        SvcParam::Mandatory(vec![SvcParamKey::Alpn, SvcParamKey::Ipv4Hint]),
+        SvcParam::Alpn(vec![b"h2".to_vec()]),
+        SvcParam::Ipv4Hint(vec![Ipv4Addr::new(192, 0, 2, 1)]),
+    ]); "unsorted mandatory")]
+    #[test_case("1 . mandatory=ipv4hint,alpn,ipv4hint alpn=h2 ipv4hint=192.0.2.1" => Err(()); "duplicate mandatory")]
+    #[test_case("1 . alpn=f\\\\\\092oo\\092,bar,h2" => Ok(vec![
+        SvcParam::Alpn(vec![b"f\\oo,bar".to_vec(), b"h2".to_vec()]),
+    ]); "escaped alpn")]
+    #[test_case("1 . alpn=h2\\\\" => Err(()); "trailing alpn escape")]
+    #[test_case("1 . mandatory=port" => Err(()); "missing mandatory")]
+    #[test_case("1 . no-default-alpn" => Err(()); "no alpn")]
+    #[test_case("1 . port=443 port=853" => Err(()); "duplicate")]
+    #[test_case("1 . port" => Err(()); "missing value")]
+    #[test_case("1 . key65535=x" => Err(()); "invalid key")]
+    fn params(rdata: &str) -> Result<Vec<SvcParam>, ()> {
+        read_zone(
+            &format!("_dns.example.com. 3600 IN SVCB {}", rdata),
+            Vec::new().into(),
+        )
+        .map(|root| {
+            let name = "_dns.example.com.".parse::<DomainName>().unwrap();
+
+            match root
+                .find(&name)
+                .unwrap()
+                .resource_record_set(RecordClass::In, RecordType::Svcb)
+            {
+                [Record::Svcb(record)] => record.params().to_vec(),
+                _ => unreachable!(),
+            }
+        })
+        .map_err(|_| ())
+    }
+
+    #[test]
+    fn zone_escaped_alpn() {
+        let record = Record::Svcb(record(
+            1,
+            ".",
+            vec![SvcParam::Alpn(vec![b"a,b\\c".to_vec(), b"h2".to_vec()])],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test]
+    fn mandatory_sorted() {
+        let record = record(
+            1,
+            ".",
+            vec![
+                SvcParam::Port(853),
+                SvcParam::Alpn(vec![b"dot".to_vec()]),
+                SvcParam::Mandatory(vec![SvcParamKey::Port, SvcParamKey::Alpn]),
+            ],
+        );
+
+        assert_eq!(
+            record.param(SvcParamKey::Mandatory),
+            Some(&SvcParam::Mandatory(vec![
+                SvcParamKey::Alpn,
+                SvcParamKey::Port
+            ])),
+        );
+    }
+
+    #[test_case(b"\x00\x01\x00\x03" => true; "sorted")]
+    #[test_case(b"\x00\x03\x00\x01" => false; "unsorted")]
+    #[test_case(b"\x00\x01\x00\x01" => false; "duplicate")]
+    fn mandatory_wire(keys: &[u8]) -> bool {
+        let record = Record::Svcb(record(
+            1,
+            ".",
+            vec![
+                SvcParam::Mandatory(vec![SvcParamKey::Alpn, SvcParamKey::Port]),
+                SvcParam::Alpn(vec![b"dot".to_vec()]),
+                SvcParam::Port(853),
+            ],
+        ));
+
+        let mut wire = to_wire(&record).unwrap();
+        let start = wire
+            .windows(8)
+            .position(|window| window == b"\x00\x00\x00\x04\x00\x01\x00\x03")
+            .unwrap();
+        wire[start + 4..start + 8].copy_from_slice(keys);
+
+        from_wire::<Record>(&wire).is_ok()
+    }
+
+    #[test_case(1, "svc.example.net." => vec!["svc.example.net."]; "target")]
+    #[test_case(1, "." => vec!["_dns.example.com."]; "owner")]
+    #[test_case(0, "." => Vec::<String>::new(); "unavailable")]
+    fn additionals(priority: u16, target: &str) -> Vec<String> {
+        let record = record(priority, target, Vec::new());
+        let question = Question::new(
+            "_dns.example.com.".parse().unwrap(),
+            RecordClass::In,
+            RecordType::Svcb,
+        );
+
+        let additionals = record.additionals(&question);
+        assert!(additionals
+            .iter()
+            .all(|(_, resolve)| *resolve == ResolveType::Additional));
+
+        let mut names = additionals
+            .into_iter()
+            .map(|(question, _)| question.name().to_string())
+            .collect::<Vec<_>>();
+        names.dedup();
+        names
+    }
+}
@@ -0,0 +1,258 @@
+/// The matching type of association data holding the full certificate or public key
+pub const MATCHING_FULL: u8 = 0;
+
+/// The matching type of association data holding a SHA-256 hash
+pub const MATCHING_SHA256: u8 = 1;
+
+/// The matching type of association data holding a SHA-512 hash
+pub const MATCHING_SHA512: u8 = 2;
+
+/// Checks the association data length against the hash size of known matching types.
+pub(crate) fn is_valid_data(matching_type: u8, data: &[u8]) -> bool {
+    match matching_type {
+        MATCHING_SHA256 => data.len() == 32,
+        MATCHING_SHA512 => data.len() == 64,
+        _ => !data.is_empty(),
+    }
+}
+
+/// Implements a record type with the TLSA data format, associating a certificate or public key
+/// with the owner.
+macro_rules! tlsa_impl {
+    ($record:ident, $rtype:ident, $doc:literal) => {
+        #[doc = concat!("A ", $doc, " record")]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $record {
+            name: $crate::text::DomainName,
+            ttl: u32,
+            rclass: $crate::record::RecordClass,
+            usage: u8,
+            selector: u8,
+            matching_type: u8,
+            data: Vec<u8>,
+        }
+
+        impl $record {
+            #[doc = concat!("Constructs a new ", $doc, " record")]
+            pub fn new(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                usage: u8,
+                selector: u8,
+                matching_type: u8,
+                data: Vec<u8>,
+            ) -> Self {
+                Self {
+                    name,
+                    ttl,
+                    rclass,
+                    usage,
+                    selector,
+                    matching_type,
+                    data,
+                }
+            }
+
+            /// How the certificate is used, such as a trust anchor or an end entity
+            pub fn usage(&self) -> u8 {
+                self.usage
+            }
+
+            /// Which part of the certificate is matched, the full certificate or its public key
+            pub fn selector(&self) -> u8 {
+                self.selector
+            }
+
+            /// How the association data is matched, exactly or by a hash
+            pub fn matching_type(&self) -> u8 {
+                self.matching_type
+            }
+
+            /// The certificate association data
+            pub fn data(&self) -> &[u8] {
+                &self.data
+            }
+        }
+
+        impl<'read> $crate::record::RecordData<'read> for $record {
+            fn data_size(&self) -> usize {
+                3 + self.data.len()
+            }
+
+            fn encode_data(
+                &self,
+                writer: &mut $crate::wire::WireWrite,
+            ) -> Result<(), $crate::wire::WireError> {
+                use $crate::wire::WireEncode;
+
+                self.usage.encode(writer)?;
+                self.selector.encode(writer)?;
+                self.matching_type.encode(writer)?;
+                writer.write(&self.data)?;
+
+                Ok(())
+            }
+
+            fn decode_data(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                len: u16,
+                reader: &mut $crate::wire::WireRead<'read>,
+            ) -> Result<Self, $crate::wire::WireError> {
+                use $crate::wire::WireDecode;
+
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                if len < 3 {
+                    return Err($crate::wire::WireError::InvalidLength {
+                        expected: 3,
+                        actual: len as usize,
+                    });
+                }
+
+                let usage = u8::decode(reader)?;
+                let selector = u8::decode(reader)?;
+                let matching_type = u8::decode(reader)?;
+
+                let mut data = vec![0; len as usize - 3];
+                reader.read(&mut data)?;
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    usage,
+                    selector,
+                    matching_type,
+                    data,
+                })
+            }
+
+            fn decode_zone(
+                name: $crate::text::DomainName,
+                ttl: u32,
+                rclass: $crate::record::RecordClass,
+                rtype: $crate::record::RecordType,
+                reader: &mut $crate::zone::ZoneReader,
+            ) -> Result<Self, $crate::zone::ZoneError> {
+                debug_assert_eq!(rtype, $crate::record::RecordType::$rtype);
+
+                let usage = reader.read_parsable()?;
+                reader.read_blank()?;
+                let selector = reader.read_parsable()?;
+                reader.read_blank()?;
+                let matching_type = reader.read_parsable()?;
+                reader.read_blank()?;
+                let data = reader.read_hex()?;
+
+                if !$crate::record::tlsa::is_valid_data(matching_type, &data) {
+                    return reader.error($crate::zone::ZoneErrorKind::BadEntry);
+                }
+
+                Ok(Self {
+                    name,
+                    ttl,
+                    rclass,
+                    usage,
+                    selector,
+                    matching_type,
+                    data,
+                })
+            }
+
+            fn name(&self) -> &$crate::text::DomainName {
+                &self.name
+            }
+
+            fn ttl(&self) -> u32 {
+                self.ttl
+            }
+
+            fn rclass(&self) -> $crate::record::RecordClass {
+                self.rclass
+            }
+
+            fn rtype(&self) -> $crate::record::RecordType {
+                $crate::record::RecordType::$rtype
+            }
+        }
+
+        impl std::fmt::Display for $record {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(
+                    f,
+                    "{} {} {} {}",
+                    self.usage,
+                    self.selector,
+                    self.matching_type,
+                    hex::encode(&self.data),
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use tlsa_impl;
+
+tlsa_impl!(TlsaRecord, Tlsa, "TLSA");
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        record::{round_trip, Record, RecordClass},
+        zone::read_zone,
+    };
+
+    const DIGEST: &str = "0c72ac70b745ac19998811b131d662c9ac69dbdbe7cb23e5b514b56664c5d3d6";
+
+    #[test]
+    fn wire() {
+        let record = Record::Tlsa(TlsaRecord::new(
+            "_25._tcp.mx.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            3,
+            1,
+            MATCHING_SHA256,
+            hex::decode(DIGEST).unwrap(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Tlsa(TlsaRecord::new(
+            "_25._tcp.mx.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            3,
+            1,
+            MATCHING_SHA256,
+            hex::decode(DIGEST).unwrap(),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case("3 1 1 0c72ac70b745ac19998811b131d662c9 ac69dbdbe7cb23e5b514b56664c5d3d6" => true; "split")]
+    #[test_case("3 1 1 0c72ac70" => false; "short digest")]
+    #[test_case("3 1 0 0c72ac70" => true; "full data")]
+    #[test_case("3 1 0" => false; "missing data")]
+    #[test_case("3 1 0 0c72ac7" => false; "odd digits")]
+    fn zone_data(rdata: &str) -> bool {
+        read_zone(
+            &format!("_25._tcp.mx.example.com. 3600 IN TLSA {}", rdata),
+            Vec::new().into(),
+        )
+        .is_ok()
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    text::{write_quoted, DomainName},
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader},
+};
+
+/// A URI record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    priority: u16,
+    weight: u16,
+    target: Vec<u8>,
+}
+
+impl UriRecord {
+    /// Constructs a new URI record
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        priority: u16,
+        weight: u16,
+        target: Vec<u8>,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            priority,
+            weight,
+            target,
+        }
+    }
+
+    /// The priority of the target, lowest first
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// The weight of the target between those with the same priority
+    pub fn weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// The URI of the target
+    pub fn target(&self) -> &[u8] {
+        &self.target
+    }
+}
+
+impl<'read> RecordData<'read> for UriRecord {
+    fn data_size(&self) -> usize {
+        4 + self.target.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        self.priority.encode(writer)?;
+        self.weight.encode(writer)?;
+        writer.write(&self.target)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Uri);
+
+        if len <= 4 {
+            return Err(WireError::InvalidLength {
+                expected: 5,
+                actual: len as usize,
+            });
+        }
+
+        let priority = u16::decode(reader)?;
+        let weight = u16::decode(reader)?;
+
+        let mut target = vec![0; len as usize - 4];
+        reader.read(&mut target)?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            priority,
+            weight,
+            target,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Uri);
+
+        let priority = reader.read_parsable()?;
+        reader.read_blank()?;
+        let weight = reader.read_parsable()?;
+        reader.read_blank()?;
+        let target: Vec<u8> = reader.read_text()?.into();
+
+        if target.is_empty() {
+            return reader.error(ZoneErrorKind::BadEntry);
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            priority,
+            weight,
+            target,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Uri
+    }
+}
+
+impl Display for UriRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {} ", self.priority, self.weight)?;
+        write_quoted(f, &self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+
+    use super::*;
+    use crate::record::{round_trip, Record};
+
+    #[test]
+    fn wire() {
+        let record = Record::Uri(UriRecord::new(
+            "_ftp._tcp.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            1,
+            b"ftp://ftp1.example.com/public".to_vec(),
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Uri(UriRecord::new(
+            "_http._tcp.example.com.".parse().unwrap(),
+            3600,
+            RecordClass::In,
+            10,
+            1,
+            b"http://www.example.com/path".to_vec(),
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    record::{RecordClass, RecordData, RecordType},
+    serial::Serial,
+    text::DomainName,
+    wire::{WireDecode, WireEncode, WireError, WireRead, WireWrite},
+    zone::{ZoneError, ZoneErrorKind, ZoneReader},
+};
+
+/// A ZONEMD record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZonemdRecord {
+    name: DomainName,
+    ttl: u32,
+    rclass: RecordClass,
+    serial: Serial,
+    scheme: u8,
+    algorithm: u8,
+    digest: Vec<u8>,
+}
+
+impl ZonemdRecord {
+    /// The scheme of a digest over the whole zone as a single file
+    pub const SIMPLE: u8 = 1;
+
+    /// The hash algorithm of a SHA-384 digest
+    pub const SHA384: u8 = 1;
+
+    /// The hash algorithm of a SHA-512 digest
+    pub const SHA512: u8 = 2;
+
+    /// Constructs a new ZONEMD record
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        serial: Serial,
+        scheme: u8,
+        algorithm: u8,
+        digest: Vec<u8>,
+    ) -> Self {
+        Self {
+            name,
+            ttl,
+            rclass,
+            serial,
+            scheme,
+            algorithm,
+            digest,
+        }
+    }
+
+    /// The serial of the SOA record the digest was computed for
+    pub fn serial(&self) -> Serial {
+        self.serial
+    }
+
+    /// The scheme the zone was collated with for the digest
+    pub fn scheme(&self) -> u8 {
+        self.scheme
+    }
+
+    /// The hash algorithm used to produce the digest
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    /// The digest of the zone
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// The length of the digests produced by a hash algorithm, `None` if it isn't known
+    pub fn digest_len(algorithm: u8) -> Option<usize> {
+        match algorithm {
+            Self::SHA384 => Some(48),
+            Self::SHA512 => Some(64),
+            _ => None,
+        }
+    }
+}
+
+impl<'read> RecordData<'read> for ZonemdRecord {
+    fn data_size(&self) -> usize {
+        6 + self.digest.len()
+    }
+
+    fn encode_data(&self, writer: &mut WireWrite) -> Result<(), WireError> {
+        u32::from(self.serial).encode(writer)?;
+        self.scheme.encode(writer)?;
+        self.algorithm.encode(writer)?;
+        writer.write(&self.digest)?;
+
+        Ok(())
+    }
+
+    fn decode_data(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        len: u16,
+        reader: &mut WireRead<'read>,
+    ) -> Result<Self, WireError> {
+        debug_assert_eq!(rtype, RecordType::Zonemd);
+
+        // Digests are at least 12 bytes long
+        if len < 18 {
+            return Err(WireError::InvalidLength {
+                expected: 18,
+                actual: len as usize,
+            });
+        }
+
+        let serial = u32::decode(reader)?.into();
+        let scheme = u8::decode(reader)?;
+        let algorithm = u8::decode(reader)?;
+
+        let mut digest = vec![0; len as usize - 6];
+        reader.read(&mut digest)?;
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            serial,
+            scheme,
+            algorithm,
+            digest,
+        })
+    }
+
+    fn decode_zone(
+        name: DomainName,
+        ttl: u32,
+        rclass: RecordClass,
+        rtype: RecordType,
+        reader: &mut ZoneReader,
+    ) -> Result<Self, ZoneError> {
+        debug_assert_eq!(rtype, RecordType::Zonemd);
+
+        let serial = reader.read_parsable::<u32>()?.into();
+        reader.read_blank()?;
+        let scheme = reader.read_parsable()?;
+        reader.read_blank()?;
+        let algorithm = reader.read_parsable()?;
+        reader.read_blank()?;
+        let digest = reader.read_hex()?;
+
+        let valid = match Self::digest_len(algorithm) {
+            Some(len) => digest.len() == len,
+            None => digest.len() >= 12,
+        };
+
+        if !valid {
+            return reader.error(ZoneErrorKind::BadEntry);
+        }
+
+        Ok(Self {
+            name,
+            ttl,
+            rclass,
+            serial,
+            scheme,
+            algorithm,
+            digest,
+        })
+    }
+
+    fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    fn rclass(&self) -> RecordClass {
+        self.rclass
+    }
+
+    fn rtype(&self) -> RecordType {
+        RecordType::Zonemd
+    }
+}
+
+impl Display for ZonemdRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            u32::from(self.serial),
+            self.scheme,
+            self.algorithm,
+            hex::encode(&self.digest),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::{assert_debug_snapshot, assert_display_snapshot};
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        record::{round_trip, Record},
+        zone::read_zone,
+    };
+
+    #[test]
+    fn wire() {
+        let record = Record::Zonemd(ZonemdRecord::new(
+            "example.com.".parse().unwrap(),
+            86400,
+            RecordClass::In,
+            2018031900.into(),
+            ZonemdRecord::SIMPLE,
+            ZonemdRecord::SHA384,
+            vec![0xab; 48],
+        ));
+
+        assert_debug_snapshot!(round_trip::wire(&record));
+    }
+
+    #[test]
+    fn zone() {
+        let record = Record::Zonemd(ZonemdRecord::new(
+            "example.com.".parse().unwrap(),
+            86400,
+            RecordClass::In,
+            2018031900.into(),
+            ZonemdRecord::SIMPLE,
+            ZonemdRecord::SHA384,
+            vec![0xab; 48],
+        ));
+
+        assert_display_snapshot!(record);
+        round_trip::zone(&record);
+    }
+
+    #[test_case(1, 48 => true; "sha384")]
+    #[test_case(2, 64 => true; "sha512")]
+    #[test_case(1, 64 => false; "sha384 wrong length")]
+    #[test_case(240, 12 => true; "private algorithm")]
+    #[test_case(240, 8 => false; "short digest")]
+    fn digest_length(algorithm: u8, len: usize) -> bool {
+        read_zone(
+            &format!(
+                "example.com. 86400 IN ZONEMD 2018031900 1 {} {}",
+                algorithm,
+                "ab".repeat(len)
+            ),
+            Vec::new().into(),
+        )
+        .is_ok()
+    }
+}
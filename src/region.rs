@@ -0,0 +1,161 @@
+use std::net::IpAddr;
+
+use crate::{cidr::Cidr, opt::client_subnet::ClientSubnetOpt};
+
+/// A database mapping blocks of addresses to region names, used to pick regional records
+#[derive(Debug, Clone, Default)]
+pub struct RegionDatabase {
+    /// The blocks of each prefix length, longest prefix first, with the blocks sorted so they can
+    /// be binary searched
+    prefixes: Vec<(u8, Vec<(Cidr, String)>)>,
+}
+
+impl RegionDatabase {
+    /// Creates an empty database, where every client is in the default region
+    pub fn new() -> Self {
+        Self {
+            prefixes: Vec::new(),
+        }
+    }
+
+    /// Creates a database from blocks of addresses and their regions. More specific blocks take
+    /// precedence, and the first of two equal blocks is used.
+    pub fn from_networks(mut networks: Vec<(Cidr, String)>) -> Self {
+        // The sort is stable, so the first of two equal blocks stays in front.
+        networks.sort_by(|(left, _), (right, _)| {
+            right
+                .prefix()
+                .cmp(&left.prefix())
+                .then_with(|| left.cmp(right))
+        });
+
+        let mut prefixes = Vec::<(u8, Vec<(Cidr, String)>)>::new();
+        for (network, region) in networks {
+            match prefixes.last_mut() {
+                Some((prefix, blocks)) if *prefix == network.prefix() => {
+                    blocks.push((network, region))
+                }
+                _ => prefixes.push((network.prefix(), vec![(network, region)])),
+            }
+        }
+
+        Self { prefixes }
+    }
+
+    /// Reads a database from CSV, with a network and a region in the first two columns of each
+    /// row. Extra columns, a header row starting with `network`, blank lines and lines starting
+    /// with `#` are ignored, so GeoLite2 block files can be used as they are. Rows without a
+    /// region are skipped. Returns the line number of the first malformed row on error.
+    pub fn read(source: &str) -> Result<Self, usize> {
+        let mut networks = Vec::new();
+
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut columns = line.split(',').map(str::trim);
+            let network = columns.next().unwrap_or_default();
+            let region = columns.next().unwrap_or_default();
+
+            if index == 0 && network == "network" {
+                continue;
+            }
+
+            let network = network.parse().map_err(|_| index + 1)?;
+            if !region.is_empty() {
+                networks.push((network, region.to_string()));
+            }
+        }
+
+        Ok(Self::from_networks(networks))
+    }
+
+    /// Finds the most specific block containing a subnet of a given prefix length. Only one
+    /// binary search is done for each prefix length in the database.
+    pub fn lookup(&self, address: IpAddr, prefix: u8) -> Option<(&Cidr, &str)> {
+        self.prefixes
+            .iter()
+            .filter(|(length, _)| *length <= prefix)
+            .find_map(|(length, blocks)| {
+                let block = Cidr::containing(address, *length)?;
+                let index = blocks.partition_point(|(other, _)| *other < block);
+
+                blocks
+                    .get(index)
+                    .filter(|(other, _)| *other == block)
+                    .map(|(network, region)| (network, region.as_str()))
+            })
+    }
+
+    /// Selects the region of a client by its client subnet, or by its address if it didn't give
+    /// one. Also returns the scope prefix length to echo in the client subnet option.
+    pub fn select(
+        &self,
+        addr: IpAddr,
+        client_subnet: Option<&ClientSubnetOpt>,
+    ) -> (Option<&str>, u8) {
+        // A source prefix of zero asks for the address of the client not to be used.
+        let subnet = client_subnet
+            .filter(|subnet| subnet.source_prefix() > 0)
+            .and_then(|subnet| Some((subnet.address()?, subnet.source_prefix())));
+
+        match subnet {
+            Some((address, source_prefix)) => match self.lookup(address, source_prefix) {
+                Some((network, region)) => (Some(region), network.prefix()),
+                None => (None, source_prefix),
+            },
+            None => {
+                let prefix = match addr {
+                    IpAddr::V4(_) => 32,
+                    IpAddr::V6(_) => 128,
+                };
+
+                (self.lookup(addr, prefix).map(|(_, region)| region), 0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const DATABASE: &str = "\
+network,region
+# Documentation ranges
+192.0.2.0/24,eu
+192.0.2.128/25,us
+2001:db8::/32,ap
+198.51.100.0/24,
+";
+
+    #[test_case("192.0.2.1", None => (Some("eu".to_string()), 0); "address")]
+    #[test_case("192.0.2.200", None => (Some("us".to_string()), 0); "most specific")]
+    #[test_case("2001:db8::1", None => (Some("ap".to_string()), 0); "ipv6")]
+    #[test_case("198.51.100.1", None => (None, 0); "no region")]
+    #[test_case("203.0.113.1", Some(("192.0.2.0", 24)) => (Some("eu".to_string()), 24); "client subnet")]
+    #[test_case("203.0.113.1", Some(("192.0.2.128", 28)) => (Some("us".to_string()), 25); "client subnet most specific")]
+    #[test_case("192.0.2.1", Some(("203.0.113.0", 24)) => (None, 24); "client subnet unknown")]
+    #[test_case("192.0.2.1", Some(("0.0.0.0", 0)) => (Some("eu".to_string()), 0); "client subnet opt out")]
+    fn select(addr: &str, client_subnet: Option<(&str, u8)>) -> (Option<String>, u8) {
+        let database = RegionDatabase::read(DATABASE).unwrap();
+
+        let client_subnet = client_subnet
+            .map(|(address, prefix)| ClientSubnetOpt::new(address.parse().unwrap(), prefix, 0));
+        let (region, scope) = database.select(addr.parse().unwrap(), client_subnet.as_ref());
+
+        (region.map(str::to_string), scope)
+    }
+
+    #[test]
+    fn read_invalid() {
+        assert_eq!(
+            RegionDatabase::read("192.0.2.0/24,eu\nexample,us\n").unwrap_err(),
+            2
+        );
+    }
+}
@@ -1,15 +1,22 @@
+use std::{borrow::Cow, sync::Arc};
+
 use colored::Colorize;
 use log::trace;
 
 use crate::{
-    context::QueryContext,
+    context::{AnyPolicy, QueryContext, Transport},
     message::{Message, Opcode, PacketType, ResponseCode},
     node::Node,
-    opt::{OptData, OptHandleAction},
-    record::{Record, RecordClass, RecordData, RecordType},
-    text::{DomainName, Name},
+    opt::{expire::ExpireOpt, extended_error::ExtendedErrorCode, OptData, OptHandleAction},
+    question::Question,
+    record::{hinfo::HinfoRecord, txt::TxtRecord, Record, RecordClass, RecordData, RecordType},
+    text::{DomainName, Text},
+    zone::Zone,
 };
 
+/// The TTL of the HINFO record synthesised for ANY queries
+const ANY_HINFO_TTL: u32 = 3600;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResolveType {
     Question,
@@ -31,15 +38,15 @@ fn find_authorities(node: &Node, qclass: RecordClass) -> &[Record] {
     &[]
 }
 
-fn find_node<'root>(
+fn find_node<'zone>(
     name: &DomainName,
     qclass: RecordClass,
-    root: &'root Node,
-) -> (Option<&'root Node>, &'root [Record]) {
-    let mut node = Some(root);
-    let mut authorities = find_authorities(root, qclass);
+    zone: &'zone Zone,
+) -> (Option<&'zone Node>, &'zone [Record]) {
+    let mut node = Some(zone.apex());
+    let mut authorities = find_authorities(zone.apex(), qclass);
 
-    for label in name.labels().iter().rev() {
+    for label in zone.relative_labels(name).unwrap_or_default().iter().rev() {
         node = node.and_then(|node| node.get(label));
         if let Some(node) = node {
             let node_authorities = find_authorities(node, qclass);
@@ -53,6 +60,121 @@ fn find_node<'root>(
     (node, authorities)
 }
 
+/// Picks the records of a type to serve to a client, preferring those of its region. The scope
+/// of the region is only reported when the type has regional records at all.
+fn select_record_set<'node>(
+    node: &'node Node,
+    rclass: RecordClass,
+    rtype: RecordType,
+    (region, region_scope): (Option<&str>, u8),
+    context: &mut QueryContext,
+) -> &'node [Record] {
+    if !node.is_regional(rclass, rtype) {
+        return node.resource_record_set(rclass, rtype);
+    }
+
+    context.client_subnet_scope = context.client_subnet_scope.max(region_scope);
+
+    region
+        .and_then(|region| node.regional_record_set(region, rclass, rtype))
+        .unwrap_or_else(|| node.resource_record_set(rclass, rtype))
+}
+
+/// Picks the answers to an ANY query according to the configured policy (RFC 8482). The record
+/// sets to choose from are selected by region in the same way as for other query types.
+fn any_answers<'node>(
+    node: &'node Node,
+    question: &Question,
+    region: (Option<&str>, u8),
+    response: &mut Message,
+    context: &mut QueryContext,
+) -> Cow<'node, [Record]> {
+    let regional_keys = region
+        .0
+        .and_then(|region| node.regions().get(region))
+        .into_iter()
+        .flat_map(|records| records.keys());
+    let mut rtypes = node
+        .records()
+        .keys()
+        .chain(regional_keys)
+        .filter(|(rclass, _)| *rclass == question.qclass())
+        .map(|(_, rtype)| *rtype)
+        .collect::<Vec<_>>();
+    rtypes.sort_by_key(|rtype| u16::from(*rtype));
+    rtypes.dedup();
+
+    let record_sets = rtypes
+        .into_iter()
+        .map(|rtype| select_record_set(node, question.qclass(), rtype, region, context))
+        .filter(|records| !records.is_empty())
+        .collect::<Vec<_>>();
+
+    if record_sets.is_empty() {
+        return Cow::Borrowed(&[]);
+    }
+
+    match context.config.server.any_policy {
+        AnyPolicy::Hinfo => Cow::Owned(vec![HinfoRecord::new(
+            question.name().clone(),
+            ANY_HINFO_TTL,
+            question.qclass(),
+            Text::from(b"RFC8482".to_vec()),
+            Text::from(Vec::new()),
+        )
+        .into()]),
+        AnyPolicy::Rrset => Cow::Borrowed(record_sets[0]),
+        AnyPolicy::Tcp => match context.connection.lock().unwrap().transport {
+            Transport::Tcp => Cow::Owned(
+                record_sets
+                    .into_iter()
+                    .flat_map(|records| records.iter().cloned())
+                    .collect(),
+            ),
+            Transport::Udp => {
+                response.set_truncated(true);
+                Cow::Borrowed(&[])
+            }
+        },
+    }
+}
+
+/// Answers the CH TXT names that identify the server. These names are never looked up in zones,
+/// so `false` is only returned for other questions.
+fn resolve_chaos(question: &Question, response: &mut Message, context: &QueryContext) -> bool {
+    if question.qclass() != RecordClass::Ch {
+        return false;
+    }
+
+    let chaos = &context.config.chaos;
+    let server = &context.config.server;
+    let identity = &server.identity_name[..];
+    // The identity names are hidden along with the NSID option when the identity is disabled.
+    let (enabled, data) = match question.name().to_string().to_ascii_lowercase().as_str() {
+        "version.bind." => (chaos.version_bind, chaos.version.as_bytes()),
+        "version.server." => (chaos.version_server, chaos.version.as_bytes()),
+        "hostname.bind." => (chaos.hostname_bind && server.identity_enabled, identity),
+        "id.server." => (chaos.id_server && server.identity_enabled, identity),
+        _ => return false,
+    };
+
+    if !enabled {
+        response.set_response_code(ResponseCode::QueryRefused);
+        return true;
+    }
+
+    if matches!(question.qtype(), RecordType::Txt | RecordType::Any) {
+        // A character string can't be longer than 255 bytes.
+        let data = data[..data.len().min(255)].to_vec();
+
+        response.add_answer(
+            TxtRecord::single(question.name().clone(), 0, RecordClass::Ch, data.into()).into(),
+        );
+    }
+
+    true
+}
+
 fn resolve_query(query: &Message, response: &mut Message, context: &mut QueryContext) {
     let mut queue = query
         .questions()
@@ -60,16 +182,31 @@ fn resolve_query(query: &Message, response: &mut Message, context: &mut QueryCon
         .map(|question| (question.clone(), ResolveType::Question))
         .collect::<Vec<_>>();
 
+    let views = Arc::clone(&context.views);
+    let addr = context.connection.lock().unwrap().addr.ip();
+    let (zones, scope) = views.select(addr, context.client_subnet.as_ref());
+    context.client_subnet_scope = context.client_subnet_scope.max(scope);
+
+    let regions = Arc::clone(&context.regions);
+    let region = regions.select(addr, context.client_subnet.as_ref());
+
     while let Some((question, resolve_type)) = queue.pop() {
         if !context.resolved.insert(question.clone()) {
             continue;
         }
 
-        let (node, authorities) =
-            find_node(question.name(), question.qclass(), &context.server.root);
+        let (node, authorities) = match zones.find(question.name()) {
+            Some(zone) => find_node(question.name(), question.qclass(), zone),
+            None => (None, &[][..]),
+        };
 
         if authorities.is_empty() && resolve_type == ResolveType::Question {
-            response.set_response_code(ResponseCode::QueryRefused);
+            response
+                .set_response_code(ResponseCode::QueryRefused)
+                .add_extended_error(
+                    ExtendedErrorCode::NotAuthoritative,
+                    "no zone is authoritative for the name",
+                );
             return;
         }
 
@@ -88,16 +225,26 @@ fn resolve_query(query: &Message, response: &mut Message, context: &mut QueryCon
         }
         let node = node.unwrap();
 
-        let mut answers = node.resource_record_set(question.qclass(), RecordType::Cname);
-        for answer in answers {
+        let mut answers =
+            Cow::Borrowed(node.resource_record_set(question.qclass(), RecordType::Cname));
+        for answer in answers.iter() {
             queue.append(&mut answer.additionals(&question));
         }
 
         if answers.is_empty() {
-            answers = node.resource_record_set(question.qclass(), question.qtype());
+            answers = match question.qtype() {
+                RecordType::Any => any_answers(node, &question, region, response, context),
+                qtype => Cow::Borrowed(select_record_set(
+                    node,
+                    question.qclass(),
+                    qtype,
+                    region,
+                    context,
+                )),
+            };
         }
 
-        for answer in answers {
+        for answer in answers.iter() {
             if resolve_type != ResolveType::Additional {
                 response.add_answer(answer.clone());
             } else {
@@ -123,19 +270,21 @@ pub async fn resolve_impl(query: &Message, context: &mut QueryContext) -> Messag
         response.add_question(question.clone());
     }
 
-    match query.edns_version() {
-        Some(version) => {
-            response.set_edns_version(Some(0));
-            response.set_udp_payload_size(context.config.server.udp_max_payload_size);
+    if let Some(version) = query.edns_version() {
+        response.set_edns_version(Some(0));
+        response.set_udp_payload_size(context.config.server.udp_max_payload_size);
 
-            if version > 0 {
-                // BADSIG and BADVERS share the same code
-                response.set_response_code(ResponseCode::BadSignature);
-                return response;
-            }
+        if version > 0 {
+            // BADSIG and BADVERS share the same code
+            response.set_response_code(ResponseCode::BadSignature);
+            return response;
         }
+    }
 
-        None => (),
+    // Only UPDATE gives the question section a different meaning, as the zone section.
+    if query.opcode() != Opcode::Update && query.questions().len() != 1 {
+        response.set_response_code(ResponseCode::FormatError);
+        return response;
     }
 
     for option in query.options() {
@@ -146,11 +295,30 @@ pub async fn resolve_impl(query: &Message, context: &mut QueryContext) -> Messag
     }
 
     if query.opcode() == Opcode::Query {
-        resolve_query(query, &mut response, context);
+        if !resolve_chaos(&query.questions()[0], &mut response, context) {
+            resolve_query(query, &mut response, context);
+        }
     } else {
         response.set_response_code(ResponseCode::NotImplemented);
     }
 
+    if let Some(client_subnet) = &context.client_subnet {
+        response.add_option(client_subnet.response(context.client_subnet_scope).into());
+    }
+
+    // Every zone is loaded from a file, so the expire time is always the full SOA expire field
+    // as it would be for a primary server.
+    if context.expire_requested {
+        let expire = response.answers().iter().find_map(|answer| match answer {
+            Record::Soa(soa) => Some(soa.expire()),
+            _ => None,
+        });
+
+        if let Some(expire) = expire {
+            response.add_option(ExpireOpt::new(Some(expire)).into());
+        }
+    }
+
     response
 }
 
@@ -166,3 +334,391 @@ pub async fn resolve(query: &Message, context: &mut QueryContext) -> Message {
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use futures::executor::block_on;
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        context::{Config, ConnectionContext, CookieStrategy, ServerContext, ZoneConfig},
+        opt::{cookie::CookieOpt, Opt},
+        region::RegionDatabase,
+        view::ViewTable,
+        zone::{read_zone, ZoneTable},
+    };
+
+    const ZONE: &str = "\
+@   3600 IN SOA ns admin 1 7200 3600 1209600 3600
+@   3600 IN NS ns
+ns  3600 IN A 192.0.2.53
+www 300  IN A 192.0.2.1
+www 300  IN AAAA 2001:db8::1
+www 300  IN TXT \"www\"
+";
+
+    /// Resolves a single question against the `example.` zone
+    fn resolve_question(
+        config: Config,
+        transport: Transport,
+        name: &str,
+        qclass: RecordClass,
+        qtype: RecordType,
+    ) -> Message {
+        resolve_in_zones(
+            &[("example.", ZONE)],
+            config,
+            RegionDatabase::new(),
+            transport,
+            name,
+            qclass,
+            qtype,
+        )
+    }
+
+    /// Resolves a single question against zones given by their origin and source
+    fn resolve_in_zones(
+        sources: &[(&str, &str)],
+        config: Config,
+        regions: RegionDatabase,
+        transport: Transport,
+        name: &str,
+        qclass: RecordClass,
+        qtype: RecordType,
+    ) -> Message {
+        let mut query = Message::new(0);
+        query.add_question(Question::new(name.parse().unwrap(), qclass, qtype));
+
+        let mut context = query_context(sources, config, regions, transport);
+        block_on(resolve_impl(&query, &mut context))
+    }
+
+    /// Resolves a whole query over UDP against the `example.` zone
+    fn resolve_message(config: Config, query: &Message) -> Message {
+        let mut context = query_context(
+            &[("example.", ZONE)],
+            config,
+            RegionDatabase::new(),
+            Transport::Udp,
+        );
+        block_on(resolve_impl(query, &mut context))
+    }
+
+    /// A context for a query from `192.0.2.100` to a server with the given zones
+    fn query_context(
+        sources: &[(&str, &str)],
+        config: Config,
+        regions: RegionDatabase,
+        transport: Transport,
+    ) -> QueryContext {
+        let mut zones = ZoneTable::new();
+        for &(origin, source) in sources {
+            let origin: DomainName = origin.parse().unwrap();
+            zones.insert(Zone::new(
+                origin.clone(),
+                read_zone(source, origin).unwrap(),
+                ZoneConfig::default(),
+            ));
+        }
+
+        let server = ServerContext::new(config, ViewTable::new(zones), regions);
+        let connection = ConnectionContext::new(
+            Arc::new(server),
+            "192.0.2.100:53".parse().unwrap(),
+            transport,
+            Duration::ZERO,
+        );
+        QueryContext::new(Arc::new(Mutex::new(connection)))
+    }
+
+    /// The info codes of the extended errors in a response
+    fn extended_errors(response: &Message) -> Vec<ExtendedErrorCode> {
+        response
+            .options()
+            .iter()
+            .filter_map(|option| match option {
+                Opt::ExtendedError(error) => Some(error.info_code()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test_case(None => Vec::<ExtendedErrorCode>::new(); "no edns")]
+    #[test_case(Some(0) => vec![ExtendedErrorCode::NotAuthoritative]; "edns")]
+    fn refused_extended_error(edns_version: Option<u8>) -> Vec<ExtendedErrorCode> {
+        let mut query = Message::new(0);
+        query
+            .set_edns_version(edns_version)
+            .add_question(Question::new(
+                "www.example.net.".parse().unwrap(),
+                RecordClass::In,
+                RecordType::A,
+            ));
+
+        let response = resolve_message(Config::default(), &query);
+        assert_eq!(response.response_code(), ResponseCode::QueryRefused);
+
+        extended_errors(&response)
+    }
+
+    #[test_case(Some(0), &[1; 8] => (ResponseCode::BadCookie, vec![ExtendedErrorCode::Prohibited]); "edns")]
+    #[test_case(None, &[] => (ResponseCode::NoError, Vec::new()); "no edns")]
+    fn bad_cookie_extended_error(
+        edns_version: Option<u8>,
+        client_cookie: &[u8],
+    ) -> (ResponseCode, Vec<ExtendedErrorCode>) {
+        let mut config = Config::default();
+        config.server.cookie_enabled = true;
+        config.server.cookie_strategy = CookieStrategy::Enforce;
+
+        // A cookie can only be sent in an OPT record, so a client without EDNS never gets
+        // BADCOOKIE.
+        let mut query = Message::new(0);
+        query
+            .set_edns_version(edns_version)
+            .add_question(Question::new(
+                "www.example.".parse().unwrap(),
+                RecordClass::In,
+                RecordType::A,
+            ));
+        if !client_cookie.is_empty() {
+            query.add_option(CookieOpt::new(client_cookie, &[]).into());
+        }
+
+        let response = resolve_message(config, &query);
+
+        (response.response_code(), extended_errors(&response))
+    }
+
+    #[test_case("example.", RecordType::Soa => vec![Some(1209600)]; "soa")]
+    #[test_case("www.example.", RecordType::A => Vec::<Option<u32>>::new(); "no soa")]
+    fn expire(name: &str, qtype: RecordType) -> Vec<Option<u32>> {
+        let mut query = Message::new(0);
+        query
+            .set_edns_version(Some(0))
+            .add_question(Question::new(name.parse().unwrap(), RecordClass::In, qtype))
+            .add_option(ExpireOpt::new(None).into());
+
+        let response = resolve_message(Config::default(), &query);
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+
+        response
+            .options()
+            .iter()
+            .filter_map(|option| match option {
+                Opt::Expire(expire) => Some(expire.expire()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test_case(AnyPolicy::Hinfo, Transport::Udp => (vec![RecordType::Hinfo], false); "hinfo")]
+    #[test_case(AnyPolicy::Rrset, Transport::Udp => (vec![RecordType::A], false); "rrset")]
+    #[test_case(AnyPolicy::Tcp, Transport::Tcp => (vec![RecordType::A, RecordType::Txt, RecordType::Aaaa], false); "tcp")]
+    #[test_case(AnyPolicy::Tcp, Transport::Udp => (Vec::new(), true); "tcp over udp")]
+    fn any(policy: AnyPolicy, transport: Transport) -> (Vec<RecordType>, bool) {
+        let mut config = Config::default();
+        config.server.any_policy = policy;
+
+        let response = resolve_question(
+            config,
+            transport,
+            "www.example.",
+            RecordClass::In,
+            RecordType::Any,
+        );
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+
+        (
+            response.answers().iter().map(Record::rtype).collect(),
+            response.truncated(),
+        )
+    }
+
+    #[test]
+    fn any_hinfo() {
+        let response = resolve_question(
+            Config::default(),
+            Transport::Udp,
+            "www.example.",
+            RecordClass::In,
+            RecordType::Any,
+        );
+
+        assert_eq!(
+            response.answers()[0].to_string(),
+            "www.example.\t3600\tIN\tHINFO\t\"RFC8482\" \"\"",
+        );
+    }
+
+    #[test_case("192.0.2.0/24" => vec!["192.0.2.2", "\"eu\"", "2001:db8::1"]; "in region")]
+    #[test_case("198.51.100.0/24" => vec!["192.0.2.1", "2001:db8::1"]; "outside region")]
+    fn any_regional(network: &str) -> Vec<String> {
+        const REGIONAL: &str = "\
+@   3600 IN SOA ns admin 1 7200 3600 1209600 3600
+@   3600 IN NS ns
+ns  3600 IN A 192.0.2.53
+www 300  IN A 192.0.2.1
+www 300  IN AAAA 2001:db8::1
+$REGION eu
+www 300  IN A 192.0.2.2
+www 300  IN TXT \"eu\"
+";
+
+        let mut config = Config::default();
+        config.server.any_policy = AnyPolicy::Tcp;
+
+        let response = resolve_in_zones(
+            &[("example.", REGIONAL)],
+            config,
+            RegionDatabase::from_networks(vec![(network.parse().unwrap(), "eu".to_string())]),
+            Transport::Tcp,
+            "www.example.",
+            RecordClass::In,
+            RecordType::Any,
+        );
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+
+        response
+            .answers()
+            .iter()
+            .map(|answer| answer.display_data().to_string())
+            .collect()
+    }
+
+    #[test_case(AnyPolicy::Hinfo; "hinfo")]
+    #[test_case(AnyPolicy::Rrset; "rrset")]
+    #[test_case(AnyPolicy::Tcp; "tcp")]
+    fn any_nonexistent(policy: AnyPolicy) {
+        let mut config = Config::default();
+        config.server.any_policy = policy;
+
+        let response = resolve_question(
+            config,
+            Transport::Udp,
+            "missing.example.",
+            RecordClass::In,
+            RecordType::Any,
+        );
+
+        assert_eq!(response.response_code(), ResponseCode::NonExistentDomain);
+        assert!(response.answers().is_empty());
+        assert!(!response.truncated());
+    }
+
+    #[derive(Clone, Copy)]
+    enum ChaosState {
+        Enabled,
+        Disabled,
+        IdentityOff,
+    }
+
+    fn chaos_config(state: ChaosState) -> Config {
+        let mut config = Config::default();
+        config.server.identity_name = b"ns1.example".to_vec();
+        config.chaos.version = "realm 1.0".to_string();
+
+        match state {
+            ChaosState::Enabled => (),
+            ChaosState::Disabled => {
+                config.chaos.version_bind = false;
+                config.chaos.version_server = false;
+                config.chaos.hostname_bind = false;
+                config.chaos.id_server = false;
+            }
+            ChaosState::IdentityOff => config.server.identity_enabled = false,
+        }
+
+        config
+    }
+
+    #[test_case("version.bind.", ChaosState::Enabled => (ResponseCode::NoError, Some("\"realm 1.0\"".to_string())); "version.bind enabled")]
+    #[test_case("version.bind.", ChaosState::Disabled => (ResponseCode::QueryRefused, None); "version.bind disabled")]
+    #[test_case("version.bind.", ChaosState::IdentityOff => (ResponseCode::NoError, Some("\"realm 1.0\"".to_string())); "version.bind identity off")]
+    #[test_case("version.server.", ChaosState::Enabled => (ResponseCode::NoError, Some("\"realm 1.0\"".to_string())); "version.server enabled")]
+    #[test_case("version.server.", ChaosState::Disabled => (ResponseCode::QueryRefused, None); "version.server disabled")]
+    #[test_case("version.server.", ChaosState::IdentityOff => (ResponseCode::NoError, Some("\"realm 1.0\"".to_string())); "version.server identity off")]
+    #[test_case("hostname.bind.", ChaosState::Enabled => (ResponseCode::NoError, Some("\"ns1.example\"".to_string())); "hostname.bind enabled")]
+    #[test_case("hostname.bind.", ChaosState::Disabled => (ResponseCode::QueryRefused, None); "hostname.bind disabled")]
+    #[test_case("hostname.bind.", ChaosState::IdentityOff => (ResponseCode::QueryRefused, None); "hostname.bind identity off")]
+    #[test_case("id.server.", ChaosState::Enabled => (ResponseCode::NoError, Some("\"ns1.example\"".to_string())); "id.server enabled")]
+    #[test_case("id.server.", ChaosState::Disabled => (ResponseCode::QueryRefused, None); "id.server disabled")]
+    #[test_case("id.server.", ChaosState::IdentityOff => (ResponseCode::QueryRefused, None); "id.server identity off")]
+    #[test_case("ID.Server.", ChaosState::Enabled => (ResponseCode::NoError, Some("\"ns1.example\"".to_string())); "case insensitive")]
+    fn chaos(name: &str, state: ChaosState) -> (ResponseCode, Option<String>) {
+        let response = resolve_question(
+            chaos_config(state),
+            Transport::Udp,
+            name,
+            RecordClass::Ch,
+            RecordType::Txt,
+        );
+
+        assert!(response.answers().len() <= 1);
+        (
+            response.response_code(),
+            response.answers().first().map(|answer| {
+                assert_eq!(answer.rclass(), RecordClass::Ch);
+                assert_eq!(answer.ttl(), 0);
+                answer.to_string().rsplit('\t').next().unwrap().to_string()
+            }),
+        )
+    }
+
+    #[test_case(RecordType::A; "a")]
+    #[test_case(RecordType::Any; "any")]
+    fn chaos_other_type(qtype: RecordType) {
+        let response = resolve_question(
+            chaos_config(ChaosState::Enabled),
+            Transport::Udp,
+            "version.bind.",
+            RecordClass::Ch,
+            qtype,
+        );
+
+        assert_eq!(response.response_code(), ResponseCode::NoError);
+        assert_eq!(
+            response.answers().len(),
+            usize::from(qtype == RecordType::Any),
+        );
+    }
+
+    #[test]
+    fn chaos_not_from_zones() {
+        const BIND: &str = "\
+@            3600 CH SOA ns.example. admin.example. 1 7200 3600 1209600 3600
+version      3600 CH TXT \"from the zone\"
+hostname     3600 CH TXT \"from the zone\"
+";
+
+        for name in ["version.bind.", "hostname.bind."] {
+            let response = resolve_in_zones(
+                &[("example.", ZONE), ("bind.", BIND)],
+                chaos_config(ChaosState::Enabled),
+                RegionDatabase::new(),
+                Transport::Udp,
+                name,
+                RecordClass::Ch,
+                RecordType::Txt,
+            );
+            assert_eq!(response.response_code(), ResponseCode::NoError);
+            assert_eq!(response.answers().len(), 1);
+            assert!(!response.answers()[0].to_string().contains("from the zone"));
+
+            let response = resolve_in_zones(
+                &[("example.", ZONE), ("bind.", BIND)],
+                chaos_config(ChaosState::Disabled),
+                RegionDatabase::new(),
+                Transport::Udp,
+                name,
+                RecordClass::Ch,
+                RecordType::Txt,
+            );
+            assert_eq!(response.response_code(), ResponseCode::QueryRefused);
+            assert!(response.answers().is_empty());
+        }
+    }
+}
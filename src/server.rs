@@ -14,12 +14,27 @@ use tokio::{
 };
 
 use crate::{
-    context::{ConnectionContext, QueryContext, ServerContext},
-    message::{Message, PacketType, ResponseCode},
+    context::{ConnectionContext, QueryContext, ServerContext, Transport},
+    message::{Message, ResponseCode},
+    opt::extended_error::ExtendedErrorCode,
     resolver,
     wire::{from_wire, to_wire},
 };
 
+/// Encodes a SERVFAIL response for a query whose response couldn't be encoded. Returns `None` if
+/// the query is too short to respond to.
+fn encode_failure(packet: &[u8], response: &Message) -> Option<Vec<u8>> {
+    let mut failure = Message::error_response(packet, ResponseCode::ServerFailure)?;
+    failure
+        .set_edns_version(response.edns_version())
+        .set_udp_payload_size(response.udp_payload_size())
+        // None of the registered codes covers failing to encode an answer that was found, and
+        // Network Error is meant for upstream servers that can't be reached.
+        .add_extended_error(ExtendedErrorCode::OtherError, "failed to encode response");
+
+    to_wire(&failure).ok()
+}
+
 pub struct UdpDnsServer {
     context: Arc<ServerContext>,
 }
@@ -30,11 +45,9 @@ impl UdpDnsServer {
     }
 
     pub async fn run(self) -> Result<(), Box<dyn Error>> {
-        let socket = Arc::new(UdpSocket::bind(self.context.config.server.udp_bind_addr).await?);
-        info!(
-            "Listening for UDP on {}",
-            self.context.config.server.udp_bind_addr,
-        );
+        let bind_addr = self.context.snapshot().config.server.udp_bind_addr;
+        let socket = Arc::new(UdpSocket::bind(bind_addr).await?);
+        info!("Listening for UDP on {}", bind_addr);
 
         let (tx, mut rx) = mpsc::channel::<([u8; 512], usize, SocketAddr)>(1024);
 
@@ -42,46 +55,43 @@ impl UdpDnsServer {
         tokio::spawn(async move {
             while let Some((packet, len, addr)) = rx.recv().await {
                 let mut payload_size = 512;
+                let mut query_context =
+                    QueryContext::new(Arc::new(Mutex::new(ConnectionContext::new(
+                        Arc::clone(&self.context),
+                        addr,
+                        Transport::Udp,
+                        Duration::ZERO,
+                    ))));
 
                 let mut response = match from_wire::<Message>(&packet[..len]) {
                     Ok(message) => {
                         payload_size = message.udp_payload_size() as usize;
-                        resolver::resolve(
-                            &message,
-                            &mut QueryContext::new(Arc::new(Mutex::new(ConnectionContext::new(
-                                Arc::clone(&self.context),
-                                addr,
-                                Duration::ZERO,
-                            )))),
-                        )
-                        .await
+                        resolver::resolve(&message, &mut query_context).await
                     }
                     Err(err) => {
                         error!("Error decoding packet: {}", err);
 
-                        let mut response = Message::new(u16::from_be_bytes([packet[0], packet[1]]));
-                        response
-                            .set_packet_type(PacketType::Response)
-                            .set_response_code(ResponseCode::FormatError);
-                        response
+                        match Message::error_response(&packet[..len], ResponseCode::FormatError) {
+                            Some(response) => response,
+                            None => continue,
+                        }
                     }
                 };
 
-                response.truncate_to(
-                    payload_size.min(self.context.config.server.udp_max_payload_size as usize),
-                );
+                let config = &query_context.config;
+                let max_size = payload_size.min(config.server.udp_max_payload_size as usize);
+                response.truncate_to(max_size);
+                response.pad_to_block(config.server.padding_block_size as usize, max_size);
 
                 let wire = match to_wire(&response) {
                     Ok(bytes) => bytes,
                     Err(err) => {
                         error!("Error encoding packet: {}", err);
 
-                        let mut response = Message::new(u16::from_be_bytes([packet[0], packet[1]]));
-                        response
-                            .set_packet_type(PacketType::Response)
-                            .set_response_code(ResponseCode::ServerFailure);
-
-                        to_wire(&response).unwrap()
+                        match encode_failure(&packet[..len], &response) {
+                            Some(bytes) => bytes,
+                            None => continue,
+                        }
                     }
                 };
 
@@ -108,11 +118,9 @@ impl TcpDnsServer {
     }
 
     pub async fn run(self) -> Result<(), Box<dyn Error>> {
-        let listener = TcpListener::bind(self.context.config.server.tcp_bind_addr).await?;
-        info!(
-            "Listening for TCP on {}",
-            self.context.config.server.tcp_bind_addr,
-        );
+        let bind_addr = self.context.snapshot().config.server.tcp_bind_addr;
+        let listener = TcpListener::bind(bind_addr).await?;
+        info!("Listening for TCP on {}", bind_addr);
 
         loop {
             let (mut stream, addr) = listener.accept().await?;
@@ -122,6 +130,7 @@ impl TcpDnsServer {
                 let conn_context = Arc::new(Mutex::new(ConnectionContext::new(
                     Arc::clone(&context),
                     addr,
+                    Transport::Tcp,
                     Duration::from_secs(300),
                 )));
 
@@ -137,38 +146,33 @@ impl TcpDnsServer {
                         return;
                     };
 
-                    let response = match from_wire::<Message>(&packet[..]) {
-                        Ok(message) => {
-                            resolver::resolve(
-                                &message,
-                                &mut QueryContext::new(Arc::clone(&conn_context)),
-                            )
-                            .await
-                        }
+                    let mut query_context = QueryContext::new(Arc::clone(&conn_context));
+                    let mut response = match from_wire::<Message>(&packet[..]) {
+                        Ok(message) => resolver::resolve(&message, &mut query_context).await,
                         Err(err) => {
                             error!("Error decoding packet: {}", err);
 
-                            let mut response =
-                                Message::new(u16::from_be_bytes([packet[0], packet[1]]));
-                            response
-                                .set_packet_type(PacketType::Response)
-                                .set_response_code(ResponseCode::FormatError);
-                            response
+                            match Message::error_response(&packet, ResponseCode::FormatError) {
+                                Some(response) => response,
+                                None => continue,
+                            }
                         }
                     };
 
+                    response.pad_to_block(
+                        query_context.config.server.padding_block_size as usize,
+                        u16::MAX as usize,
+                    );
+
                     let wire = match to_wire(&response) {
                         Ok(bytes) => bytes,
                         Err(err) => {
                             error!("Error encoding packet: {}", err);
 
-                            let mut response =
-                                Message::new(u16::from_be_bytes([packet[0], packet[1]]));
-                            response
-                                .set_packet_type(PacketType::Response)
-                                .set_response_code(ResponseCode::ServerFailure);
-
-                            to_wire(&response).unwrap()
+                            match encode_failure(&packet, &response) {
+                                Some(bytes) => bytes,
+                                None => continue,
+                            }
                         }
                     };
 
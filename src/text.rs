@@ -1,4 +1,5 @@
 use std::{
+    cmp::Ordering,
     error::Error,
     fmt::{self, Debug, Display, Formatter},
     hash::{Hash, Hasher},
@@ -124,7 +125,7 @@ pub struct Label(Vec<u8>);
 
 impl Label {
     /// An iterator of normalized bytes for comparisons between two labels
-    fn normalized_bytes(&self) -> Map<Iter<u8>, fn(&u8) -> u8> {
+    fn normalized_bytes(&self) -> Map<Iter<'_, u8>, fn(&u8) -> u8> {
         self.0.iter().map(u8::to_ascii_uppercase)
     }
 }
@@ -136,7 +137,10 @@ impl WireEncode for Label {
 
     fn encode(&self, writer: &mut WireWrite) -> Result<(), WireError> {
         (self.0.len() as u8).encode(writer)?;
-        writer.write(&self.0)?;
+        match writer.is_canonical() {
+            true => writer.write(&self.0.to_ascii_lowercase())?,
+            false => writer.write(&self.0)?,
+        }
 
         Ok(())
     }
@@ -162,6 +166,8 @@ impl Display for Label {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         for byte in &self.0 {
             match byte {
+                // These would otherwise be read as part of the zone file syntax
+                b';' | b'(' | b')' | b'"' => write!(f, "\\{:0>3}", byte)?,
                 b'!'..=b'~' => {
                     if *byte == b'\\' || *byte == b'.' {
                         write!(f, "\\")?;
@@ -202,6 +208,22 @@ impl PartialEq for Label {
     }
 }
 
+/// Labels are ordered as described by the canonical DNS name order in RFC 4034
+impl Ord for Label {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .iter()
+            .map(u8::to_ascii_lowercase)
+            .cmp(other.0.iter().map(u8::to_ascii_lowercase))
+    }
+}
+
+impl PartialOrd for Label {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Hash for Label {
     fn hash<H>(&self, state: &mut H)
     where
@@ -358,6 +380,19 @@ macro_rules! name_impl {
             }
         }
 
+        /// Names are ordered as described by the canonical DNS name order in RFC 4034
+        impl Ord for $type {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.iter().rev().cmp(other.0.iter().rev())
+            }
+        }
+
+        impl PartialOrd for $type {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
         impl FromStr for $type {
             type Err = TextParseError;
 
@@ -435,6 +470,13 @@ impl From<DomainName> for HostName {
 #[derive(Clone, PartialEq, Eq)]
 pub struct Text(Vec<u8>);
 
+impl Text {
+    /// The raw bytes of the string
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl WireEncode for Text {
     fn size(&self) -> usize {
         self.0.len() + 1
@@ -464,23 +506,29 @@ impl Debug for Text {
     }
 }
 
-impl Display for Text {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "\"")?;
-        for byte in &self.0 {
-            match byte {
-                b' '..=b'~' => {
-                    if *byte == b'\\' || *byte == b'"' {
-                        write!(f, "\\")?;
-                    }
-                    write!(f, "{}", *byte as char)?;
+/// Writes bytes as a quoted string, escaping quotes, backslashes and unprintable bytes. Unlike
+/// [`Text`], the bytes aren't limited to 255 of them.
+pub fn write_quoted(f: &mut Formatter, bytes: &[u8]) -> fmt::Result {
+    write!(f, "\"")?;
+    for byte in bytes {
+        match byte {
+            b' '..=b'~' => {
+                if *byte == b'\\' || *byte == b'"' {
+                    write!(f, "\\")?;
                 }
-                _ => write!(f, "\\{:0>3}", byte)?,
+                write!(f, "{}", *byte as char)?;
             }
+            _ => write!(f, "\\{:0>3}", byte)?,
         }
-        write!(f, "\"")?;
+    }
+    write!(f, "\"")?;
 
-        Ok(())
+    Ok(())
+}
+
+impl Display for Text {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_quoted(f, &self.0)
     }
 }
 
@@ -542,6 +590,7 @@ mod tests {
     #[test_case("example-label".as_bytes(), "example-label."; "basic")]
     #[test_case("escaped.dot".as_bytes(), "escaped\\.dot."; "escaped dot")]
     #[test_case(&[0, 1, 255], "\\000\\001\\255."; "escaped bytes")]
+    #[test_case("(zone;syntax)".as_bytes(), "\\040zone\\059syntax\\041."; "escaped syntax")]
     #[test_case(&[], "."; "empty")]
     fn label_fmt(label: &[u8], formatted: &str) {
         assert_eq!(Label::from_str(formatted), Ok(Label(label.to_vec())));
@@ -564,6 +613,16 @@ mod tests {
         assert_eq!(from_wire::<Label>(wire).unwrap(), label);
     }
 
+    #[test]
+    fn label_wire_canonical() {
+        let mut writer = WireWrite::canonical();
+        Label("WwW".as_bytes().to_vec())
+            .encode(&mut writer)
+            .unwrap();
+
+        assert_eq!(writer.buffer(), b"\x03www");
+    }
+
     #[test_case(Label("hello".as_bytes().to_vec()), Label("hello".as_bytes().to_vec()), true; "basic")]
     #[test_case(Label("test".as_bytes().to_vec()), Label("TEST".as_bytes().to_vec()), true; "case insensitive")]
     #[test_case(Label("hotdog".as_bytes().to_vec()), Label("sandwich".as_bytes().to_vec()), false; "inequality")]
@@ -0,0 +1,340 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{
+    node::Node,
+    record::{Record, RecordClass, RecordData, RecordType},
+    text::{DomainName, HostName, Label, Name},
+    zonemd::{self, Verification},
+};
+
+/// How serious a validation issue is, zones with errors should not be served
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssueKind {
+    CnameAndOtherData,
+    MultipleCnames,
+    MissingSoa,
+    DuplicateSoa,
+    MissingGlue(HostName),
+    CnameTarget(RecordType, HostName),
+    OutOfZone,
+    TtlMismatch(RecordClass, RecordType),
+    ZonemdMismatch,
+}
+
+impl ValidationIssueKind {
+    /// The severity of this kind of issue
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::CnameTarget(_, _) | Self::TtlMismatch(_, _) | Self::ZonemdMismatch => {
+                Severity::Warning
+            }
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Display for ValidationIssueKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::CnameAndOtherData => write!(f, "CNAME and other data"),
+            Self::MultipleCnames => write!(f, "multiple CNAME records"),
+            Self::MissingSoa => write!(f, "no SOA record at the zone apex"),
+            Self::DuplicateSoa => write!(f, "multiple SOA records at the zone apex"),
+            Self::MissingGlue(target) => {
+                write!(f, "NS target {} has no address records", target)
+            }
+            Self::CnameTarget(rtype, target) => {
+                write!(f, "{} target {} is a CNAME", rtype, target)
+            }
+            Self::OutOfZone => write!(f, "record is outside of the zone origin"),
+            Self::TtlMismatch(rclass, rtype) => {
+                write!(f, "{} {} RRset has mismatched TTLs", rclass, rtype)
+            }
+            Self::ZonemdMismatch => write!(f, "ZONEMD digest doesn't match the zone"),
+        }
+    }
+}
+
+/// A problem found with the data of a zone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    name: DomainName,
+    kind: ValidationIssueKind,
+}
+
+impl ValidationIssue {
+    pub fn new(name: DomainName, kind: ValidationIssueKind) -> Self {
+        Self { name, kind }
+    }
+
+    /// The owner name the issue was found at
+    pub fn name(&self) -> &DomainName {
+        &self.name
+    }
+
+    pub fn kind(&self) -> &ValidationIssueKind {
+        &self.kind
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity(), self.name, self.kind)
+    }
+}
+
+fn is_subdomain<N>(name: &N, origin: &DomainName) -> bool
+where
+    N: Name,
+{
+    name.labels().ends_with(origin.labels())
+}
+
+/// Validates a zone read into a root node, returning any issues found in no particular order.
+pub fn validate_zone(root: &Node, origin: &DomainName) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    let apex_soa_count = root
+        .find(origin)
+        .map(|apex| {
+            apex.records()
+                .iter()
+                .filter(|((_, rtype), _)| *rtype == RecordType::Soa)
+                .map(|(_, records)| records.len())
+                .sum()
+        })
+        .unwrap_or(0);
+    match apex_soa_count {
+        0 => issues.push(ValidationIssue::new(
+            origin.clone(),
+            ValidationIssueKind::MissingSoa,
+        )),
+        1 => (),
+        _ => issues.push(ValidationIssue::new(
+            origin.clone(),
+            ValidationIssueKind::DuplicateSoa,
+        )),
+    }
+
+    if zonemd::verify(root, origin) == Verification::Mismatch {
+        issues.push(ValidationIssue::new(
+            origin.clone(),
+            ValidationIssueKind::ZonemdMismatch,
+        ));
+    }
+
+    let mut nodes = vec![(Vec::<Label>::new(), root)];
+    while let Some((path, node)) = nodes.pop() {
+        for (label, child) in node.children() {
+            let mut path = path.clone();
+            path.push(label.clone());
+            nodes.push((path, child));
+        }
+
+        if node.records().is_empty() && node.regions().is_empty() {
+            continue;
+        }
+
+        let name = DomainName::from(path.into_iter().rev().collect::<Vec<_>>());
+
+        if !is_subdomain(&name, origin) {
+            issues.push(ValidationIssue::new(name, ValidationIssueKind::OutOfZone));
+            continue;
+        }
+
+        validate_node(root, origin, &name, node, &mut issues);
+    }
+
+    issues
+}
+
+fn validate_node(
+    root: &Node,
+    origin: &DomainName,
+    name: &DomainName,
+    node: &Node,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    validate_record_sets(root, origin, name, node.records(), None, issues);
+
+    // Clients in a region are served its RRsets in place of the default ones of the same type.
+    for record_sets in node.regions().values() {
+        validate_record_sets(
+            root,
+            origin,
+            name,
+            record_sets,
+            Some(node.records()),
+            issues,
+        );
+    }
+}
+
+/// Validates the RRsets of a name, either its default ones or those of a region. `defaults` are
+/// the default RRsets served alongside those of a region, `None` when validating the defaults.
+fn validate_record_sets(
+    root: &Node,
+    origin: &DomainName,
+    name: &DomainName,
+    record_sets: &HashMap<(RecordClass, RecordType), Vec<Record>>,
+    defaults: Option<&HashMap<(RecordClass, RecordType), Vec<Record>>>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut issue = |kind| issues.push(ValidationIssue::new(name.clone(), kind));
+
+    let keys = |record_sets: &HashMap<(RecordClass, RecordType), Vec<Record>>| {
+        record_sets
+            .iter()
+            .filter(|(_, records)| !records.is_empty())
+            .map(|(key, _)| *key)
+            .collect::<HashSet<_>>()
+    };
+    let has_cname_and_other_data = |keys: &HashSet<(RecordClass, RecordType)>, rclass| {
+        keys.contains(&(rclass, RecordType::Cname))
+            && keys.iter().any(|&(other_rclass, other_rtype)| {
+                other_rclass == rclass && other_rtype != RecordType::Cname
+            })
+    };
+
+    let own = keys(record_sets);
+    let defaults = defaults.map(keys).unwrap_or_default();
+    let served = own.union(&defaults).copied().collect::<HashSet<_>>();
+
+    let rclasses = own
+        .iter()
+        .map(|&(rclass, _)| rclass)
+        .collect::<HashSet<_>>();
+    for rclass in rclasses {
+        // A conflict between default RRsets alone is reported when validating the defaults, so
+        // a region only reports the ones its own RRsets take part in.
+        if has_cname_and_other_data(&served, rclass) && !has_cname_and_other_data(&defaults, rclass)
+        {
+            issue(ValidationIssueKind::CnameAndOtherData);
+        }
+    }
+
+    for (&(rclass, rtype), records) in record_sets {
+        if records.is_empty() {
+            continue;
+        }
+
+        if rtype == RecordType::Cname && records.len() > 1 {
+            issue(ValidationIssueKind::MultipleCnames);
+        }
+
+        if records
+            .iter()
+            .any(|record| record.ttl() != records[0].ttl())
+        {
+            issue(ValidationIssueKind::TtlMismatch(rclass, rtype));
+        }
+
+        for record in records {
+            let (target, target_rtype) = match record {
+                Record::Ns(ns) => {
+                    let target = ns.authority();
+                    if !is_subdomain(target, origin) {
+                        continue;
+                    }
+
+                    let has_address = root.find(target).is_some_and(|target| {
+                        !target.resource_record_set(rclass, RecordType::A).is_empty()
+                            || !target
+                                .resource_record_set(rclass, RecordType::Aaaa)
+                                .is_empty()
+                    });
+                    if !has_address {
+                        issue(ValidationIssueKind::MissingGlue(target.clone()));
+                    }
+
+                    continue;
+                }
+                Record::Mx(mx) => (mx.exchange(), RecordType::Mx),
+                Record::Srv(srv) => (srv.target(), RecordType::Srv),
+                _ => continue,
+            };
+
+            let is_cname = root.find(target).is_some_and(|target| {
+                !target
+                    .resource_record_set(rclass, RecordType::Cname)
+                    .is_empty()
+            });
+            if is_cname {
+                issue(ValidationIssueKind::CnameTarget(
+                    target_rtype,
+                    target.clone(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::zone::read_zone;
+
+    const SOA: &str = "@ 3600 IN SOA ns.example. admin.example. 1 7200 3600 1209600 3600\n";
+
+    #[test_case("www 3600 IN A 192.0.2.1\n" => Vec::<ValidationIssueKind>::new(); "valid")]
+    #[test_case("www 3600 IN CNAME host.example.\nwww 3600 IN A 192.0.2.1\n" => vec![ValidationIssueKind::CnameAndOtherData]; "cname and other data")]
+    #[test_case("@ 3600 IN SOA ns.example. admin.example. 2 7200 3600 1209600 3600\n" => vec![ValidationIssueKind::DuplicateSoa]; "duplicate soa")]
+    #[test_case("@ 3600 IN NS ns.example.\n" => vec![ValidationIssueKind::MissingGlue("ns.example.".parse().unwrap())]; "missing glue")]
+    #[test_case("@ 3600 IN NS ns.example.net.\n" => Vec::<ValidationIssueKind>::new(); "out of zone ns")]
+    #[test_case("@ 3600 IN MX 10 mail.example.\nmail 3600 IN CNAME host.example.net.\n" => vec![ValidationIssueKind::CnameTarget(RecordType::Mx, "mail.example.".parse().unwrap())]; "mx cname target")]
+    #[test_case("www.example.net. 3600 IN A 192.0.2.1\n" => vec![ValidationIssueKind::OutOfZone]; "out of zone")]
+    #[test_case("www 3600 IN A 192.0.2.1\nwww 300 IN A 192.0.2.2\n" => vec![ValidationIssueKind::TtlMismatch(RecordClass::In, RecordType::A)]; "ttl mismatch")]
+    #[test_case("www 3600 IN A 192.0.2.1\n$REGION eu\nwww 3600 IN CNAME host.example.\n" => vec![ValidationIssueKind::CnameAndOtherData]; "regional cname and default data")]
+    #[test_case("www 3600 IN CNAME host.example.\n$REGION eu\nwww 3600 IN A 192.0.2.1\n" => vec![ValidationIssueKind::CnameAndOtherData]; "default cname and regional data")]
+    #[test_case("www 3600 IN A 192.0.2.1\n$REGION eu\nwww 3600 IN A 192.0.2.2\n" => Vec::<ValidationIssueKind>::new(); "regional override")]
+    #[test_case("www 3600 IN CNAME host.example.\nwww 3600 IN A 192.0.2.1\n$REGION eu\nwww 3600 IN A 192.0.2.2\n$REGION us\nwww 3600 IN TXT \"us\"\n" => vec![ValidationIssueKind::CnameAndOtherData]; "default conflict with regions")]
+    #[test_case("$REGION eu\nwww 3600 IN A 192.0.2.1\nwww 300 IN A 192.0.2.2\n" => vec![ValidationIssueKind::TtlMismatch(RecordClass::In, RecordType::A)]; "regional ttl mismatch")]
+    #[test_case("$REGION eu\nwww.example.net. 3600 IN A 192.0.2.1\n" => vec![ValidationIssueKind::OutOfZone]; "regional out of zone")]
+    #[test_case(&format!("@ 3600 IN ZONEMD 1 1 1 {}\n", "00".repeat(48)) => vec![ValidationIssueKind::ZonemdMismatch]; "zonemd mismatch")]
+    fn validate(source: &str) -> Vec<ValidationIssueKind> {
+        let origin = "example.".parse().unwrap();
+        let root = read_zone(&format!("{}{}", SOA, source), origin).unwrap();
+
+        validate_zone(&root, &"example.".parse().unwrap())
+            .into_iter()
+            .map(|issue| issue.kind().clone())
+            .collect()
+    }
+
+    #[test]
+    fn missing_soa() {
+        let root = read_zone("www 3600 IN A 192.0.2.1\n", "example.".parse().unwrap()).unwrap();
+
+        assert_eq!(
+            validate_zone(&root, &"example.".parse().unwrap()),
+            vec![ValidationIssue::new(
+                "example.".parse().unwrap(),
+                ValidationIssueKind::MissingSoa,
+            )],
+        );
+    }
+}
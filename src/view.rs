@@ -0,0 +1,180 @@
+use std::net::IpAddr;
+
+use crate::{
+    cidr::Cidr, context::ViewConfig, opt::client_subnet::ClientSubnetOpt, zone::ZoneTable,
+};
+
+/// A set of zones served only to the clients that match it
+#[derive(Debug, Clone)]
+pub struct View {
+    name: String,
+    networks: Vec<Cidr>,
+    client_subnets: Vec<Cidr>,
+    zones: ZoneTable,
+}
+
+impl View {
+    /// Constructs a new view with the criteria from its config
+    pub fn new(config: &ViewConfig, zones: ZoneTable) -> Self {
+        Self {
+            name: config.name.clone(),
+            networks: config.networks.clone(),
+            client_subnets: config.client_subnets.clone(),
+            zones,
+        }
+    }
+
+    /// The name of the view
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The zones served to clients matching the view
+    pub fn zones(&self) -> &ZoneTable {
+        &self.zones
+    }
+
+    /// Whether a client matches the view, views without any criteria match every client. Widens
+    /// `scope` to the prefix lengths of the client subnets of the same address family that the
+    /// outcome depended on.
+    fn matches(
+        &self,
+        addr: IpAddr,
+        client_subnet: Option<&ClientSubnetOpt>,
+        scope: &mut u8,
+    ) -> bool {
+        if self.networks.is_empty() && self.client_subnets.is_empty() {
+            return true;
+        }
+
+        let subnet =
+            client_subnet.and_then(|subnet| Some((subnet.address()?, subnet.source_prefix())));
+        if let Some((address, source_prefix)) = subnet {
+            for cidr in &self.client_subnets {
+                // A block of the other family can't match, and its prefix could be longer than
+                // the address.
+                if cidr.address().is_ipv4() != address.is_ipv4() {
+                    continue;
+                }
+
+                *scope = (*scope).max(cidr.prefix());
+                if cidr.contains_subnet(address, source_prefix) {
+                    return true;
+                }
+            }
+        }
+
+        self.networks.iter().any(|cidr| cidr.contains(addr))
+    }
+}
+
+/// The views of the server, checked in order, and the zones served when none match
+#[derive(Debug, Clone, Default)]
+pub struct ViewTable {
+    views: Vec<View>,
+    default: ZoneTable,
+}
+
+impl ViewTable {
+    /// Creates a table without any views
+    pub fn new(default: ZoneTable) -> Self {
+        Self {
+            views: Vec::new(),
+            default,
+        }
+    }
+
+    /// Adds a view after every existing view
+    pub fn push(&mut self, view: View) {
+        self.views.push(view)
+    }
+
+    /// Gets a view by its name
+    pub fn get(&self, name: &str) -> Option<&View> {
+        self.views.iter().find(|view| view.name() == name)
+    }
+
+    /// The zones served to clients that don't match any view
+    pub fn default_zones(&self) -> &ZoneTable {
+        &self.default
+    }
+
+    /// Selects the zones for a client by its address and client subnet. Also returns the scope
+    /// prefix length to echo in the client subnet option.
+    pub fn select(
+        &self,
+        addr: IpAddr,
+        client_subnet: Option<&ClientSubnetOpt>,
+    ) -> (&ZoneTable, u8) {
+        let mut scope = 0;
+
+        let zones = self
+            .views
+            .iter()
+            .find(|view| view.matches(addr, client_subnet, &mut scope))
+            .map(View::zones)
+            .unwrap_or(&self.default);
+
+        (zones, scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{context::ZoneConfig, node::Node, text::Name, zone::Zone};
+
+    fn view(name: &str, networks: &[&str], client_subnets: &[&str]) -> View {
+        let mut zones = ZoneTable::new();
+        zones.insert(Zone::new(
+            format!("{}.example.", name).parse().unwrap(),
+            Node::new(),
+            ZoneConfig::default(),
+        ));
+
+        View::new(
+            &ViewConfig {
+                name: name.to_string(),
+                networks: networks.iter().map(|cidr| cidr.parse().unwrap()).collect(),
+                client_subnets: client_subnets
+                    .iter()
+                    .map(|cidr| cidr.parse().unwrap())
+                    .collect(),
+                zones: Default::default(),
+            },
+            zones,
+        )
+    }
+
+    #[test_case("10.1.2.3", None => ("internal".to_string(), 0); "client address")]
+    #[test_case("192.0.2.1", None => ("default".to_string(), 0); "no match")]
+    #[test_case("192.0.2.1", Some(("198.51.100.0", 24)) => ("partner".to_string(), 16); "client subnet")]
+    #[test_case("192.0.2.1", Some(("198.51.0.0", 8)) => ("default".to_string(), 16); "client subnet too wide")]
+    #[test_case("10.1.2.3", Some(("203.0.113.0", 24)) => ("internal".to_string(), 16); "client subnet no match")]
+    #[test_case("192.0.2.1", Some(("2001:db8:1::", 56)) => ("partner".to_string(), 48); "client subnet ipv6")]
+    #[test_case("192.0.2.1", Some(("2001:db8:2::", 56)) => ("default".to_string(), 48); "client subnet ipv6 no match")]
+    fn select(addr: &str, client_subnet: Option<(&str, u8)>) -> (String, u8) {
+        let mut views = ViewTable::new(ZoneTable::new());
+        views.push(view("partner", &[], &["198.51.0.0/16", "2001:db8:1::/48"]));
+        views.push(view("internal", &["10.0.0.0/8"], &[]));
+
+        let client_subnet = client_subnet
+            .map(|(address, prefix)| ClientSubnetOpt::new(address.parse().unwrap(), prefix, 0));
+        let (zones, scope) = views.select(addr.parse().unwrap(), client_subnet.as_ref());
+
+        let name = zones
+            .zones()
+            .next()
+            .map(|zone| {
+                zone.origin().labels()[0]
+                    .to_string()
+                    .trim_end_matches('.')
+                    .to_string()
+            })
+            .unwrap_or_else(|| "default".to_string());
+
+        (name, scope)
+    }
+}
@@ -31,18 +31,32 @@ pub trait WireEncode {
 /// A writer for binary data
 pub struct WireWrite {
     buffer: Vec<u8>,
+    canonical: bool,
 }
 
 impl WireWrite {
     /// Constructs a new writer
     pub fn new() -> Self {
-        Self { buffer: Vec::new() }
+        Self {
+            buffer: Vec::new(),
+            canonical: false,
+        }
     }
 
     /// Constructs a new writer with a preallocated capacity
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             buffer: Vec::with_capacity(capacity),
+            canonical: false,
+        }
+    }
+
+    /// Constructs a new writer that writes names in lowercase, for the canonical form of records
+    /// described in RFC 4034
+    pub fn canonical() -> Self {
+        Self {
+            buffer: Vec::new(),
+            canonical: true,
         }
     }
 
@@ -56,6 +70,11 @@ impl WireWrite {
     pub fn buffer(&self) -> &[u8] {
         &self.buffer
     }
+
+    /// Whether names are written in lowercase
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
 }
 
 impl Default for WireWrite {
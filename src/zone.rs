@@ -1,18 +1,26 @@
 use std::{
+    collections::HashMap,
     error::Error,
-    fmt::{self, Display, Formatter},
+    fmt::{self, Display, Formatter, Write},
+    path::Path,
     str::FromStr,
 };
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use logos::{Lexer, Logos, Span};
 
 use crate::{
+    context::ZoneConfig,
     node::Node,
-    record::{Record, RecordClass, RecordData, RecordType},
-    text::{parse_text, DomainName, Name, Text, TextParseResult},
-    wire::WireRead,
+    record::{soa::SoaRecord, Record, RecordClass, RecordData, RecordType},
+    text::{parse_text, DomainName, Label, Name, Text, TextParseResult},
+    wire::{WireError, WireRead},
+    zonemd,
 };
 
+/// The region given to `$REGION` to go back to records that are served to every client
+pub const DEFAULT_REGION: &str = "default";
+
 fn lex_string(lexer: &mut Lexer<ZoneToken>) -> String {
     lexer.slice().to_string()
 }
@@ -95,6 +103,30 @@ impl ZoneError {
     pub fn span(&self) -> &Span {
         &self.span
     }
+
+    /// Returns the one-based line and column of the start of this error in the given source.
+    pub fn location(&self, source: &str) -> (usize, usize) {
+        let before = &source[..self.span.start.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+
+        (line, column)
+    }
+
+    /// Pairs this error with the file it was read from, for displaying with a source snippet.
+    pub fn diagnostic<'a>(&'a self, path: &'a Path, source: &'a str) -> ZoneDiagnostic<'a> {
+        ZoneDiagnostic {
+            error: self,
+            path,
+            source,
+        }
+    }
 }
 
 impl Display for ZoneError {
@@ -105,6 +137,50 @@ impl Display for ZoneError {
 
 impl Error for ZoneError {}
 
+/// A [`ZoneError`] displayed as `file:line:column: message`, followed by the offending line
+/// and a caret underline of the span.
+#[derive(Debug)]
+pub struct ZoneDiagnostic<'a> {
+    error: &'a ZoneError,
+    path: &'a Path,
+    source: &'a str,
+}
+
+impl Display for ZoneDiagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (line, column) = self.error.location(self.source);
+
+        let start = self.error.span.start.min(self.source.len());
+        let line_start = self.source[..start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let line_end = self.source[line_start..]
+            .find(['\r', '\n'])
+            .map_or(self.source.len(), |index| line_start + index);
+        let end = self.error.span.end.clamp(start.min(line_end), line_end);
+
+        // Tabs are kept in the padding so the carets line up with the printed line.
+        let padding = self.source[line_start..start.min(line_end)]
+            .chars()
+            .map(|char| if char == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let underline = "^".repeat(self.source[start.min(line_end)..end].chars().count().max(1));
+        let gutter = " ".repeat(line.to_string().len());
+
+        writeln!(
+            f,
+            "{}:{}:{}: {}",
+            self.path.display(),
+            line,
+            column,
+            self.error.kind,
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line, &self.source[line_start..line_end])?;
+        write!(f, "{} | {}{}", gutter, padding, underline)
+    }
+}
+
 #[derive(Debug)]
 pub struct ZoneReader<'source> {
     lexer: Lexer<'source, ZoneToken>,
@@ -115,6 +191,7 @@ pub struct ZoneReader<'source> {
     name: Option<DomainName>,
     ttl: Option<u32>,
     rclass: Option<RecordClass>,
+    region: Option<String>,
 }
 
 impl<'source> ZoneReader<'source> {
@@ -134,11 +211,9 @@ impl<'source> ZoneReader<'source> {
                 }
                 self.parentheses -= 1;
             }
-            Some(ZoneToken::NewLine) | None => {
-                // If it's zero, the end of the entry has been reached.
-                if self.parentheses == 0 {
-                    return self.error(ZoneErrorKind::IncompleteEntry);
-                }
+            // If it's zero, the end of the entry has been reached.
+            Some(ZoneToken::NewLine) | None if self.parentheses == 0 => {
+                return self.error(ZoneErrorKind::IncompleteEntry);
             }
             _ => (),
         }
@@ -312,6 +387,41 @@ impl<'source> ZoneReader<'source> {
         Ok(labels.into())
     }
 
+    /// Reads the strings until the end of the entry joined together, skipping blank tokens.
+    /// Other tokens return an error of kind [`ZoneErrorKind::BadEntry`].
+    fn read_joined(&mut self) -> Result<String, ZoneError> {
+        let mut data = String::new();
+        while self.peek().is_some() {
+            match self.read()? {
+                ZoneToken::String(s) => data.push_str(&s),
+                ZoneToken::Whitespace
+                | ZoneToken::NewLine
+                | ZoneToken::OpenParen
+                | ZoneToken::CloseParen => (),
+                _ => return self.error(ZoneErrorKind::BadEntry),
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Reads hexadecimal data until the end of the entry, which may be split by blank tokens.
+    /// Invalid data returns an error of kind [`ZoneErrorKind::BadEntry`].
+    pub fn read_hex(&mut self) -> Result<Vec<u8>, ZoneError> {
+        match hex::decode(self.read_joined()?) {
+            Ok(data) => Ok(data),
+            Err(_) => self.error(ZoneErrorKind::BadEntry),
+        }
+    }
+
+    /// Similar to `ZoneReader::read_hex`, but reads base64 data.
+    pub fn read_base64(&mut self) -> Result<Vec<u8>, ZoneError> {
+        match BASE64.decode(self.read_joined()?) {
+            Ok(data) => Ok(data),
+            Err(_) => self.error(ZoneErrorKind::BadEntry),
+        }
+    }
+
     /// Similar to `ZoneReader::read`, but only takes [`ZoneToken::Text`] tokens and returns
     /// its value. Other tokens return an error of kind [`ZoneErrorKind::BadEntry`].
     pub fn read_text(&mut self) -> Result<Text, ZoneError> {
@@ -322,8 +432,121 @@ impl<'source> ZoneReader<'source> {
     }
 }
 
-/// Reads the source into a root node.
-pub fn read_zone(source: &str, origin: DomainName) -> Result<Node, ZoneError> {
+/// A zone of authority, holding the records at and below its origin
+#[derive(Debug, Clone)]
+pub struct Zone {
+    origin: DomainName,
+    apex: Node,
+    config: ZoneConfig,
+}
+
+impl Zone {
+    /// Constructs a zone from a root node, such as one returned by [`read_zone`]. Records outside
+    /// of the origin are discarded.
+    pub fn new(origin: DomainName, mut root: Node, config: ZoneConfig) -> Self {
+        for label in origin.labels().iter().rev() {
+            root = root.remove(label).unwrap_or_default();
+        }
+
+        Self {
+            origin,
+            apex: root,
+            config,
+        }
+    }
+
+    /// The name at the apex of this zone
+    pub fn origin(&self) -> &DomainName {
+        &self.origin
+    }
+
+    /// The node at the apex of this zone
+    pub fn apex(&self) -> &Node {
+        &self.apex
+    }
+
+    /// The config this zone was loaded with
+    pub fn config(&self) -> &ZoneConfig {
+        &self.config
+    }
+
+    /// The SOA record at the apex of this zone
+    pub fn soa(&self) -> Option<&SoaRecord> {
+        self.apex
+            .records()
+            .iter()
+            .filter(|((_, rtype), _)| *rtype == RecordType::Soa)
+            .flat_map(|(_, records)| records)
+            .find_map(|record| match record {
+                Record::Soa(soa) => Some(soa),
+                _ => None,
+            })
+    }
+
+    /// The labels of a name below the origin, or `None` if the name is outside of this zone
+    pub fn relative_labels<'name, N>(&self, name: &'name N) -> Option<&'name [Label]>
+    where
+        N: Name,
+    {
+        name.labels().strip_suffix(self.origin.labels())
+    }
+
+    /// Gets the node for a name in this zone
+    pub fn find<N>(&self, name: &N) -> Option<&Node>
+    where
+        N: Name,
+    {
+        self.relative_labels(name)?
+            .iter()
+            .rev()
+            .try_fold(&self.apex, |node, label| node.get(label))
+    }
+}
+
+/// A table of zones, looked up by the closest enclosing origin of a name
+#[derive(Debug, Default, Clone)]
+pub struct ZoneTable {
+    zones: HashMap<DomainName, Zone>,
+}
+
+impl ZoneTable {
+    /// Creates an empty zone table
+    pub fn new() -> Self {
+        Self {
+            zones: HashMap::new(),
+        }
+    }
+
+    /// Adds a zone to the table, returning the zone it replaced with the same origin
+    pub fn insert(&mut self, zone: Zone) -> Option<Zone> {
+        self.zones.insert(zone.origin.clone(), zone)
+    }
+
+    /// Gets a zone by its origin
+    pub fn get(&self, origin: &DomainName) -> Option<&Zone> {
+        self.zones.get(origin)
+    }
+
+    /// Finds the zone with the longest origin that contains a name
+    pub fn find<N>(&self, name: &N) -> Option<&Zone>
+    where
+        N: Name,
+    {
+        let labels = name.labels();
+
+        (0..=labels.len())
+            .find_map(|start| self.zones.get(&DomainName::from(labels[start..].to_vec())))
+    }
+
+    /// The zones in this table, in no particular order
+    pub fn zones(&self) -> impl Iterator<Item = &Zone> {
+        self.zones.values()
+    }
+}
+
+/// Reads the source into a root node. Malformed entries are skipped so that every error in the
+/// source is reported, not only the first.
+pub fn read_zone(source: &str, origin: DomainName) -> Result<Node, Vec<ZoneError>> {
     let mut reader = ZoneReader {
         lexer: Lexer::new(source),
         peeked: None,
@@ -333,38 +556,73 @@ pub fn read_zone(source: &str, origin: DomainName) -> Result<Node, ZoneError> {
         name: None,
         ttl: None,
         rclass: None,
+        region: None,
     };
+    let mut errors = Vec::new();
 
     loop {
         if reader.lexer.span().end == reader.lexer.source().len() {
             break;
         }
 
-        let is_named_resource =
-            matches!(reader.peek(), Some(ZoneToken::String(s)) if !s.starts_with('$'));
-        if is_named_resource {
-            reader.name = Some(reader.read_name()?);
-
-            // Assert that next token is whitespace, token is later swallowed by the match below.
-            match reader.peek() {
-                Some(ZoneToken::Whitespace) => {}
-                Some(_) => return reader.error(ZoneErrorKind::BadEntry),
-                None => return reader.error(ZoneErrorKind::IncompleteEntry),
-            }
+        if let Err(err) = read_entry(&mut reader) {
+            errors.push(err);
+            recover(&mut reader);
         }
+    }
 
-        match reader.read() {
-            Ok(ZoneToken::Whitespace) => handle_resource(&mut reader)?,
-            Ok(ZoneToken::String(control)) => handle_control(&mut reader, control)?,
-            Ok(_) => return reader.error(ZoneErrorKind::BadEntry),
-            Err(err) if *err.kind() == ZoneErrorKind::IncompleteEntry => (),
-            Err(err) => return Err(err),
+    match errors.is_empty() {
+        true => Ok(reader.root),
+        false => Err(errors),
+    }
+}
+
+/// Reads a single entry, including the blanks that lead up to the next entry.
+fn read_entry(reader: &mut ZoneReader) -> Result<(), ZoneError> {
+    let is_named_resource =
+        matches!(reader.peek(), Some(ZoneToken::String(s)) if !s.starts_with('$'));
+    if is_named_resource {
+        reader.name = Some(reader.read_name()?);
+
+        // Assert that next token is whitespace, token is later swallowed by the match below.
+        match reader.peek() {
+            Some(ZoneToken::Whitespace) => {}
+            Some(_) => return reader.error(ZoneErrorKind::BadEntry),
+            None => return reader.error(ZoneErrorKind::IncompleteEntry),
         }
+    }
+
+    match reader.read() {
+        Ok(ZoneToken::Whitespace) => handle_resource(reader)?,
+        Ok(ZoneToken::String(control)) => handle_control(reader, control)?,
+        Ok(_) => return reader.error(ZoneErrorKind::BadEntry),
+        Err(err) if *err.kind() == ZoneErrorKind::IncompleteEntry => (),
+        Err(err) => return Err(err),
+    }
+
+    next_entry(reader, true)
+}
 
-        next_entry(&mut reader, true)?;
+/// Skips the remainder of a malformed entry so that reading can resume at the next one.
+fn recover(reader: &mut ZoneReader) {
+    // The error may have been raised on the new line ending the entry, in which case the reader
+    // is already at the start of the next entry.
+    let at_new_line = reader.lexer.slice().starts_with(['\r', '\n']);
+    if reader.peeked.is_none() && reader.parentheses == 0 && at_new_line {
+        return;
     }
 
-    Ok(reader.root)
+    loop {
+        match reader.peeked.take().unwrap_or_else(|| reader.lexer.next()) {
+            Some(ZoneToken::OpenParen) => reader.parentheses += 1,
+            Some(ZoneToken::CloseParen) => {
+                reader.parentheses = reader.parentheses.saturating_sub(1)
+            }
+            Some(ZoneToken::NewLine) if reader.parentheses == 0 => return,
+            Some(_) => (),
+            None => return,
+        }
+    }
 }
 
 /// Reads zero or more blanks until the end of the entry, then advances to the next entry.
@@ -404,6 +662,8 @@ fn handle_resource(reader: &mut ZoneReader) -> Result<(), ZoneError> {
 
     loop {
         if let Ok(ZoneToken::String(string)) = reader.read() {
+            let span = reader.span();
+
             match reader.peek() {
                 Some(ZoneToken::Whitespace) => _ = reader.read(),
                 Some(_) => return reader.error(ZoneErrorKind::BadEntry),
@@ -423,7 +683,11 @@ fn handle_resource(reader: &mut ZoneReader) -> Result<(), ZoneError> {
                 continue;
             }
 
-            if let Ok(parsed_rtype) = string.parse() {
+            if let Ok(parsed_rtype) = string.parse::<RecordType>() {
+                if parsed_rtype.is_question_only() {
+                    return Err(ZoneError::new(ZoneErrorKind::BadEntry, span));
+                }
+
                 rtype = parsed_rtype;
                 break;
             }
@@ -437,21 +701,9 @@ fn handle_resource(reader: &mut ZoneReader) -> Result<(), ZoneError> {
             _ = reader.read();
             reader.read_blank()?;
             let size = reader.read_parsable::<usize>()?;
-            let mut buf = vec![0; size];
-
-            let mut data = String::with_capacity(size * 2);
-            while let Ok(token) = reader.read() {
-                match token {
-                    ZoneToken::String(s) => data.push_str(&s),
-                    ZoneToken::Whitespace
-                    | ZoneToken::NewLine
-                    | ZoneToken::OpenParen
-                    | ZoneToken::CloseParen => (),
-                    _ => return reader.error(ZoneErrorKind::BadEntry),
-                }
-            }
+            let buf = reader.read_hex()?;
 
-            if hex::decode_to_slice(data, &mut buf[..]).is_err() {
+            if buf.len() != size {
                 return reader.error(ZoneErrorKind::BadEntry);
             }
 
@@ -480,7 +732,10 @@ fn handle_resource(reader: &mut ZoneReader) -> Result<(), ZoneError> {
     for label in record.name().labels().iter().rev() {
         node = node.insert(label.clone());
     }
-    node.add_record(record);
+    match &reader.region {
+        Some(region) => node.add_regional_record(region.clone(), record),
+        None => node.add_record(record),
+    }
 
     Ok(())
 }
@@ -496,8 +751,380 @@ fn handle_control(reader: &mut ZoneReader, control: String) -> Result<(), ZoneEr
         "$TTL" => {
             reader.ttl = Some(reader.read_parsable()?);
         }
+        "$REGION" => {
+            let region = reader.read_string()?;
+            reader.region = (region != DEFAULT_REGION).then_some(region);
+        }
         _ => return reader.error(ZoneErrorKind::UnknownControl(control)),
     }
 
     Ok(())
 }
+
+/// Writes a root node out as a zone file. Names are written in canonical order relative to the
+/// given origin, with each name only written for the first record it owns. Records only served to
+/// a region follow in a `$REGION` block for each region. Fails if a record couldn't be encoded to
+/// be sorted.
+pub fn write_zone(root: &Node, origin: &DomainName) -> Result<String, WireError> {
+    let mut nodes = vec![(Vec::<Label>::new(), root)];
+    let mut names = Vec::new();
+
+    while let Some((path, node)) = nodes.pop() {
+        for (label, child) in node.children() {
+            let mut path = path.clone();
+            path.push(label.clone());
+            nodes.push((path, child));
+        }
+
+        if !node.records().is_empty() || !node.regions().is_empty() {
+            names.push((
+                DomainName::from(path.into_iter().rev().collect::<Vec<_>>()),
+                node,
+            ));
+        }
+    }
+
+    names.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    let mut zone = String::new();
+    writeln!(zone, "$ORIGIN {}", origin).unwrap();
+
+    for (name, node) in &names {
+        write_record_sets(&mut zone, &owner_name(name, origin), node.records())?;
+    }
+
+    let mut regions = names
+        .iter()
+        .flat_map(|(_, node)| node.regions().keys())
+        .collect::<Vec<_>>();
+    regions.sort();
+    regions.dedup();
+
+    for region in regions {
+        writeln!(zone, "$REGION {}", region).unwrap();
+
+        for (name, node) in &names {
+            if let Some(records) = node.regions().get(region) {
+                write_record_sets(&mut zone, &owner_name(name, origin), records)?;
+            }
+        }
+    }
+
+    Ok(zone)
+}
+
+/// Writes the RRsets owned by a single name, with the records of each RRset in canonical order.
+fn write_record_sets(
+    zone: &mut String,
+    owner: &str,
+    record_sets: &HashMap<(RecordClass, RecordType), Vec<Record>>,
+) -> Result<(), WireError> {
+    let mut record_sets = record_sets.iter().collect::<Vec<_>>();
+    // The SOA record conventionally comes first in a zone.
+    record_sets.sort_by_key(|((rclass, rtype), _)| {
+        (
+            *rtype != RecordType::Soa,
+            u16::from(*rtype),
+            u16::from(*rclass),
+        )
+    });
+
+    let mut owner = Some(owner);
+
+    for (_, records) in record_sets {
+        let mut records = records
+            .iter()
+            .map(|record| Ok((zonemd::canonical_data(record)?, record)))
+            .collect::<Result<Vec<_>, WireError>>()?;
+        records.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        for (_, record) in records {
+            writeln!(
+                zone,
+                "{}\t{}\t{}\t{}\t{}",
+                owner.take().unwrap_or_default(),
+                record.ttl(),
+                record.rclass(),
+                record.rtype(),
+                record.display_data(),
+            )
+            .unwrap();
+        }
+    }
+
+    Ok(())
+}
+
+/// The one-based line numbers of comments, `$ORIGIN` and `$TTL` entries in a zone file, which
+/// [`write_zone`] doesn't keep when the zone is written back out
+pub fn unwritten_lines(source: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+
+    for (index, line) in source.lines().enumerate() {
+        if line.starts_with("$ORIGIN") || line.starts_with("$TTL") {
+            lines.push(index + 1);
+            continue;
+        }
+
+        let mut quoted = false;
+        let mut chars = line.chars();
+        while let Some(char) = chars.next() {
+            match char {
+                '\\' => _ = chars.next(),
+                '"' => quoted = !quoted,
+                ';' if !quoted => {
+                    lines.push(index + 1);
+                    break;
+                }
+                _ => (),
+            }
+        }
+    }
+
+    lines
+}
+
+/// Formats the owner of a record relative to the origin when possible.
+fn owner_name(name: &DomainName, origin: &DomainName) -> String {
+    let labels = name.labels();
+
+    let owner = match labels.strip_suffix(origin.labels()) {
+        Some([]) => return "@".to_string(),
+        Some(relative) => {
+            let mut owner = relative.iter().map(ToString::to_string).collect::<String>();
+            owner.pop();
+            owner
+        }
+        None => name.to_string(),
+    };
+
+    // A lone "@" would be read as the origin and a leading "$" as a control entry.
+    if owner == "@" {
+        return r"\064".to_string();
+    }
+    match owner.strip_prefix('$') {
+        Some(rest) => format!(r"\036{}", rest),
+        None => owner,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn errors_recover() {
+        let source = "a 300 IN A bad\nb 300 IN A 192.0.2.1\nc 300 IN MX x host\n";
+        let errors = read_zone(source, "example.".parse().unwrap()).unwrap_err();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| (err.kind(), err.location(source)))
+                .collect::<Vec<_>>(),
+            vec![
+                (&ZoneErrorKind::BadEntry, (1, 12)),
+                (&ZoneErrorKind::BadEntry, (3, 13)),
+            ],
+        );
+    }
+
+    #[test_case("www 300 IN ANY 192.0.2.1\n"; "any")]
+    #[test_case("www 300 IN TYPE255 \\# 0\n"; "any number")]
+    #[test_case("www 300 IN TYPE252 \\# 0\n"; "axfr")]
+    fn question_only_type(source: &str) {
+        let errors = read_zone(source, "example.".parse().unwrap()).unwrap_err();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| (err.kind(), err.location(source)))
+                .collect::<Vec<_>>(),
+            vec![(&ZoneErrorKind::BadEntry, (1, 12))],
+        );
+    }
+
+    #[test]
+    fn diagnostic() {
+        let source = "a 300 IN A 192.0.2.1\nb\t300 IN A bad\n";
+        let errors = read_zone(source, "example.".parse().unwrap()).unwrap_err();
+
+        assert_eq!(
+            errors[0]
+                .diagnostic(Path::new("example.zone"), source)
+                .to_string(),
+            concat!(
+                "example.zone:2:12: bad zone entry\n",
+                "  |\n",
+                "2 | b\t300 IN A bad\n",
+                "  |  \t         ^^^",
+            ),
+        );
+    }
+
+    #[test]
+    fn write() {
+        let source = concat!(
+            "www 300 IN A 192.0.2.2\n",
+            "@ 3600 IN NS ns\n",
+            "@ 3600 IN SOA ns admin 1 7200 3600 1209600 3600\n",
+            "www 300 IN A 192.0.2.1\n",
+            "ns 3600 IN A 192.0.2.53\n",
+            "a.b.example.net. 60 IN TYPE65280 \\# 2 cafe\n",
+        );
+        let origin = "example.".parse().unwrap();
+        let root = read_zone(source, "example.".parse().unwrap()).unwrap();
+
+        assert_eq!(
+            write_zone(&root, &origin).unwrap(),
+            concat!(
+                "$ORIGIN example.\n",
+                "@\t3600\tIN\tSOA\tns.example. admin.example. 1 7200 3600 1209600 3600\n",
+                "\t3600\tIN\tNS\tns.example.\n",
+                "ns\t3600\tIN\tA\t192.0.2.53\n",
+                "www\t300\tIN\tA\t192.0.2.1\n",
+                "\t300\tIN\tA\t192.0.2.2\n",
+                "a.b.example.net.\t60\tIN\tTYPE65280\t\\# 2 cafe\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn write_round_trip() {
+        let source = concat!(
+            "@ 3600 IN SOA ns admin 1 7200 3600 1209600 3600\n",
+            "\\064 300 IN TXT \"at\"\n",
+            "\\036dollar 300 IN TXT \"dollar\"\n",
+            "semi\\059colon 300 IN A 192.0.2.1\n",
+            "www 300 IN A 192.0.2.1\n",
+            "www 300 IN A 192.0.2.2\n",
+            "www 300 IN MX 10 mail\n",
+            "other 60 IN TYPE65280 \\# 0\n",
+        );
+        let origin = "example.".parse().unwrap();
+        let root = read_zone(source, "example.".parse().unwrap()).unwrap();
+
+        let written = write_zone(&root, &origin).unwrap();
+        let reread = read_zone(&written, Vec::new().into());
+        assert_eq!(reread, Ok(root));
+        assert_eq!(write_zone(&reread.unwrap(), &origin), Ok(written));
+    }
+
+    #[test]
+    fn write_canonical_order() {
+        let source = concat!(
+            "@ 300 IN MX 10 B.example.\n",
+            "@ 300 IN MX 10 a.example.\n",
+            "@ 300 IN MX 10 c.example.\n",
+        );
+        let origin = "example.".parse().unwrap();
+        let root = read_zone(source, "example.".parse().unwrap()).unwrap();
+
+        assert_eq!(
+            write_zone(&root, &origin).unwrap(),
+            concat!(
+                "$ORIGIN example.\n",
+                "@\t300\tIN\tMX\t10 a.example.\n",
+                "\t300\tIN\tMX\t10 B.example.\n",
+                "\t300\tIN\tMX\t10 c.example.\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn unwritten() {
+        let source = concat!(
+            "$ORIGIN example.\n",
+            "$TTL 300\n",
+            "; A comment\n",
+            "www IN A 192.0.2.1 ; trailing\n",
+            "txt IN TXT \"not ; a comment\"\n",
+            "semi\\;colon IN A 192.0.2.2\n",
+            "$REGION eu\n",
+        );
+
+        assert_eq!(unwritten_lines(source), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn regions() {
+        let source = concat!(
+            "www 300 IN A 192.0.2.1\n",
+            "$REGION eu\n",
+            "www 300 IN A 192.0.2.2\n",
+            "$REGION default\n",
+            "mail 300 IN A 192.0.2.3\n",
+        );
+        let origin: DomainName = "example.".parse().unwrap();
+        let root = read_zone(source, origin.clone()).unwrap();
+
+        let www = root
+            .find(&"www.example.".parse::<DomainName>().unwrap())
+            .unwrap();
+        assert_eq!(
+            www.resource_record_set(RecordClass::In, RecordType::A)
+                .len(),
+            1
+        );
+        assert_eq!(
+            www.regional_record_set("eu", RecordClass::In, RecordType::A)
+                .map(<[Record]>::len),
+            Some(1),
+        );
+        assert!(www.is_regional(RecordClass::In, RecordType::A));
+        assert!(www
+            .regional_record_set("us", RecordClass::In, RecordType::A)
+            .is_none());
+
+        assert_eq!(
+            write_zone(&root, &origin).unwrap(),
+            concat!(
+                "$ORIGIN example.\n",
+                "mail\t300\tIN\tA\t192.0.2.3\n",
+                "www\t300\tIN\tA\t192.0.2.1\n",
+                "$REGION eu\n",
+                "www\t300\tIN\tA\t192.0.2.2\n",
+            ),
+        );
+    }
+
+    #[test_case("www.sub.example." => Some("sub.example.".to_string()); "child zone")]
+    #[test_case("sub.example." => Some("sub.example.".to_string()); "child apex")]
+    #[test_case("www.example." => Some("example.".to_string()); "parent zone")]
+    #[test_case("example.net." => None; "no zone")]
+    fn zone_table_find(name: &str) -> Option<String> {
+        let mut zones = ZoneTable::new();
+        for origin in ["example.", "sub.example."] {
+            zones.insert(Zone::new(
+                origin.parse().unwrap(),
+                Node::new(),
+                ZoneConfig::default(),
+            ));
+        }
+
+        zones
+            .find(&name.parse::<DomainName>().unwrap())
+            .map(|zone| zone.origin().to_string())
+    }
+
+    #[test]
+    fn zone_apex() {
+        let source = "@ 3600 IN SOA ns admin 1 7200 3600 1209600 3600\nwww 300 IN A 192.0.2.1\n";
+        let origin: DomainName = "example.".parse().unwrap();
+        let zone = Zone::new(
+            origin.clone(),
+            read_zone(source, origin).unwrap(),
+            ZoneConfig::default(),
+        );
+
+        assert_eq!(zone.soa().map(|soa| u32::from(soa.serial())), Some(1));
+        assert!(zone
+            .find(&"www.example.".parse::<DomainName>().unwrap())
+            .is_some());
+        assert!(zone
+            .find(&"www.example.net.".parse::<DomainName>().unwrap())
+            .is_none());
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    iter,
+};
+
+use sha2::{Digest, Sha384, Sha512};
+
+use crate::{
+    node::Node,
+    record::{zonemd::ZonemdRecord, Record, RecordData, RecordType},
+    text::{DomainName, Label, Name},
+    wire::{WireEncode, WireError, WireWrite},
+};
+
+/// The outcome of checking the ZONEMD records at the apex of a zone against its contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    /// There are no ZONEMD records at the apex
+    Absent,
+    /// None of the ZONEMD records use a supported scheme and hash algorithm
+    Unsupported,
+    /// A ZONEMD record matches the zone
+    Verified,
+    /// None of the supported ZONEMD records match the zone
+    Mismatch,
+}
+
+/// An error computing the digest of a zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZonemdError {
+    /// The hash algorithm isn't SHA-384 or SHA-512
+    UnsupportedAlgorithm(u8),
+    /// There is no SOA record at the apex to take the serial from
+    MissingSoa,
+    /// A record in the zone couldn't be encoded
+    Encode(WireError),
+}
+
+impl Display for ZonemdError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "unsupported ZONEMD hash algorithm {}", algorithm)
+            }
+            Self::MissingSoa => write!(f, "no SOA record at the zone apex"),
+            Self::Encode(err) => write!(f, "couldn't encode a record in the zone: {}", err),
+        }
+    }
+}
+
+impl Error for ZonemdError {}
+
+impl From<WireError> for ZonemdError {
+    fn from(err: WireError) -> Self {
+        Self::Encode(err)
+    }
+}
+
+/// Whether the names in the data of a record type are lowercased in its canonical form, as listed
+/// in RFC 4034 and amended by RFC 6840
+fn has_canonical_names(rtype: RecordType) -> bool {
+    matches!(
+        rtype,
+        RecordType::Ns
+            | RecordType::Cname
+            | RecordType::Soa
+            | RecordType::Ptr
+            | RecordType::Mx
+            | RecordType::Rp
+            | RecordType::Afsdb
+            | RecordType::Rt
+            | RecordType::Naptr
+            | RecordType::Kx
+            | RecordType::Srv
+    )
+}
+
+/// Whether a record is left out of the digest, which are the ZONEMD records at the apex and the
+/// signatures covering them
+fn is_excluded(record: &Record, origin: &DomainName) -> bool {
+    if record.name() != origin {
+        return false;
+    }
+
+    match record {
+        Record::Zonemd(_) => true,
+        Record::Other(other) if other.rtype() == RecordType::Other(46) => other
+            .data()
+            .starts_with(&u16::from(RecordType::Zonemd).to_be_bytes()),
+        _ => false,
+    }
+}
+
+/// Encodes the data of a record in its canonical form, which is also the order records of an RRset
+/// are sorted in
+pub fn canonical_data(record: &Record) -> Result<Vec<u8>, WireError> {
+    let mut writer = match has_canonical_names(record.rtype()) {
+        true => WireWrite::canonical(),
+        false => WireWrite::new(),
+    };
+    record.encode_data(&mut writer)?;
+
+    Ok(writer.buffer().to_vec())
+}
+
+/// A record in the canonical wire format described in RFC 4034, alongside the fields it's
+/// ordered by
+struct CanonicalRecord {
+    name: DomainName,
+    rclass: u16,
+    rtype: u16,
+    data: Vec<u8>,
+    wire: Vec<u8>,
+}
+
+impl CanonicalRecord {
+    fn new(record: &Record) -> Result<Self, WireError> {
+        let data = canonical_data(record)?;
+
+        let mut writer = WireWrite::canonical();
+        record.name().encode(&mut writer)?;
+        u16::from(record.rtype()).encode(&mut writer)?;
+        u16::from(record.rclass()).encode(&mut writer)?;
+        record.ttl().encode(&mut writer)?;
+        (data.len() as u16).encode(&mut writer)?;
+        writer.write(&data)?;
+
+        Ok(Self {
+            name: record.name().clone(),
+            rclass: record.rclass().into(),
+            rtype: record.rtype().into(),
+            data,
+            wire: writer.buffer().to_vec(),
+        })
+    }
+}
+
+/// The records of a zone in canonical form, ordered by owner name, class, type and data, with
+/// duplicates removed. Records only served to a region are not part of the digest.
+fn canonical_records(root: &Node, origin: &DomainName) -> Result<Vec<CanonicalRecord>, WireError> {
+    let mut records = Vec::new();
+
+    let Some(apex) = root.find(origin) else {
+        return Ok(records);
+    };
+
+    let mut nodes = vec![(origin.labels().to_vec(), apex)];
+    while let Some((labels, node)) = nodes.pop() {
+        for (label, child) in node.children() {
+            let labels = iter::once(label.clone())
+                .chain(labels.iter().cloned())
+                .collect::<Vec<Label>>();
+            nodes.push((labels, child));
+        }
+
+        for record in node.records().values().flatten() {
+            if !is_excluded(record, origin) {
+                records.push(CanonicalRecord::new(record)?);
+            }
+        }
+    }
+
+    records.sort_by(|left, right| {
+        left.name
+            .cmp(&right.name)
+            .then(left.rclass.cmp(&right.rclass))
+            .then(left.rtype.cmp(&right.rtype))
+            .then(left.data.cmp(&right.data))
+    });
+    records.dedup_by(|left, right| {
+        left.name == right.name
+            && left.rclass == right.rclass
+            && left.rtype == right.rtype
+            && left.data == right.data
+    });
+
+    Ok(records)
+}
+
+fn hash<D>(records: &[CanonicalRecord]) -> Vec<u8>
+where
+    D: Digest,
+{
+    let mut hasher = D::new();
+    for record in records {
+        hasher.update(&record.wire);
+    }
+
+    hasher.finalize().to_vec()
+}
+
+/// Computes the digest of a zone read into a root node with the simple scheme described in
+/// RFC 8976
+pub fn digest(root: &Node, origin: &DomainName, algorithm: u8) -> Result<Vec<u8>, ZonemdError> {
+    match algorithm {
+        ZonemdRecord::SHA384 => Ok(hash::<Sha384>(&canonical_records(root, origin)?)),
+        ZonemdRecord::SHA512 => Ok(hash::<Sha512>(&canonical_records(root, origin)?)),
+        _ => Err(ZonemdError::UnsupportedAlgorithm(algorithm)),
+    }
+}
+
+/// Checks the ZONEMD records at the apex of a zone read into a root node against its contents. A
+/// single supported record that matches the zone and the serial of its SOA record is enough.
+pub fn verify(root: &Node, origin: &DomainName) -> Verification {
+    let Some(apex) = root.find(origin) else {
+        return Verification::Absent;
+    };
+
+    let serial = apex
+        .records()
+        .values()
+        .flatten()
+        .find_map(|record| match record {
+            Record::Soa(soa) => Some(soa.serial()),
+            _ => None,
+        });
+
+    let zonemds = apex
+        .records()
+        .values()
+        .flatten()
+        .filter_map(|record| match record {
+            Record::Zonemd(zonemd) => Some(zonemd),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if zonemds.is_empty() {
+        return Verification::Absent;
+    }
+
+    let mut verification = Verification::Unsupported;
+    for zonemd in zonemds {
+        if zonemd.scheme() != ZonemdRecord::SIMPLE {
+            continue;
+        }
+
+        match digest(root, origin, zonemd.algorithm()) {
+            Ok(digest) if Some(zonemd.serial()) == serial && digest == zonemd.digest() => {
+                return Verification::Verified;
+            }
+            Err(ZonemdError::UnsupportedAlgorithm(_)) => continue,
+            _ => verification = Verification::Mismatch,
+        }
+    }
+
+    verification
+}
+
+/// Replaces the ZONEMD records at the apex of a zone read into a root node with a SHA-384 digest of
+/// its contents. The zone is left as it was on error.
+pub fn refresh(root: &mut Node, origin: &DomainName) -> Result<(), ZonemdError> {
+    let Some(soa) = root.find(origin).and_then(|apex| {
+        apex.records()
+            .values()
+            .flatten()
+            .find_map(|record| match record {
+                Record::Soa(soa) => Some(soa.clone()),
+                _ => None,
+            })
+    }) else {
+        return Err(ZonemdError::MissingSoa);
+    };
+
+    // The ZONEMD records being replaced aren't part of the digest.
+    let digest = digest(root, origin, ZonemdRecord::SHA384)?;
+
+    let mut apex = root;
+    for label in origin.labels().iter().rev() {
+        apex = apex.insert(label.clone());
+    }
+
+    apex.records_mut()
+        .retain(|(_, rtype), _| *rtype != RecordType::Zonemd);
+    apex.add_record(Record::Zonemd(ZonemdRecord::new(
+        origin.clone(),
+        soa.ttl(),
+        soa.rclass(),
+        soa.serial(),
+        ZonemdRecord::SIMPLE,
+        ZonemdRecord::SHA384,
+        digest,
+    )));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{record::RecordClass, zone::read_zone};
+
+    const ZONE: &str = "\
+@       86400 IN SOA ns1 admin 2018031900 1800 900 604800 86400
+@       86400 IN NS ns1
+@       86400 IN NS ns2
+ns1     3600  IN A 203.0.113.63
+ns2     3600  IN AAAA 2001:db8::63
+";
+
+    fn origin() -> DomainName {
+        "example.".parse().unwrap()
+    }
+
+    #[test]
+    fn digest_ignores_case_order_and_duplicates() {
+        let root = read_zone(ZONE, origin()).unwrap();
+        let shuffled = read_zone(
+            "\
+NS2     3600  IN AAAA 2001:db8::63
+@       86400 IN NS NS2
+ns1     3600  IN A 203.0.113.63
+@       86400 IN NS ns1
+@       86400 IN NS ns1
+@       86400 IN SOA NS1 admin 2018031900 1800 900 604800 86400
+",
+            origin(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            digest(&root, &origin(), ZonemdRecord::SHA384),
+            digest(&shuffled, &origin(), ZonemdRecord::SHA384),
+        );
+        assert_eq!(
+            digest(&root, &origin(), ZonemdRecord::SHA512)
+                .unwrap()
+                .len(),
+            64
+        );
+        assert_eq!(
+            digest(&root, &origin(), 240),
+            Err(ZonemdError::UnsupportedAlgorithm(240))
+        );
+    }
+
+    #[test]
+    fn refresh_and_verify() {
+        let mut root = read_zone(ZONE, origin()).unwrap();
+        assert_eq!(verify(&root, &origin()), Verification::Absent);
+
+        assert_eq!(refresh(&mut root, &origin()), Ok(()));
+        assert_eq!(verify(&root, &origin()), Verification::Verified);
+
+        let zonemds = root
+            .find(&origin())
+            .unwrap()
+            .resource_record_set(RecordClass::In, RecordType::Zonemd);
+        assert_eq!(zonemds.len(), 1);
+
+        // A second refresh replaces the record rather than adding another.
+        assert_eq!(refresh(&mut root, &origin()), Ok(()));
+        assert_eq!(verify(&root, &origin()), Verification::Verified);
+
+        let mut changed = root.clone();
+        changed.merge(read_zone("www 3600 IN A 203.0.113.80\n", origin()).unwrap());
+        assert_eq!(verify(&changed, &origin()), Verification::Mismatch);
+    }
+
+    #[test]
+    fn verify_serial() {
+        let mut root = read_zone(ZONE, origin()).unwrap();
+        assert_eq!(refresh(&mut root, &origin()), Ok(()));
+
+        let zonemd = root
+            .find(&origin())
+            .unwrap()
+            .resource_record_set(RecordClass::In, RecordType::Zonemd)[0]
+            .to_string()
+            .replace("2018031900", "2018031901");
+        let mut stale = read_zone(ZONE, origin()).unwrap();
+        stale.merge(read_zone(&zonemd, origin()).unwrap());
+
+        assert_eq!(verify(&stale, &origin()), Verification::Mismatch);
+    }
+
+    #[test]
+    fn verify_unsupported() {
+        let mut root = read_zone(ZONE, origin()).unwrap();
+        root.merge(
+            read_zone(
+                &format!("@ 86400 IN ZONEMD 2018031900 1 240 {}\n", "00".repeat(12)),
+                origin(),
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(verify(&root, &origin()), Verification::Unsupported);
+    }
+
+    #[test]
+    fn refresh_without_soa() {
+        let mut root = read_zone("www 3600 IN A 203.0.113.80\n", origin()).unwrap();
+        let original = root.clone();
+
+        assert_eq!(refresh(&mut root, &origin()), Err(ZonemdError::MissingSoa));
+        assert_eq!(root, original);
+    }
+}